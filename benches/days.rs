@@ -0,0 +1,37 @@
+//! Benchmarks of every day's solution against the example input of its puzzle.
+
+use aoc2025::Solution;
+use aoc2025::input::Input;
+use aoc2025::{day_1, day_2, day_3, day_4, day_5, day_6, day_7};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+/// Benchmarks parsing and each half of the puzzle separately, in a group named after the day.
+fn bench_solution<S: Solution>(c: &mut Criterion, name: &str, example: &str) {
+    let mut group = c.benchmark_group(name);
+    let example = Input::new(example);
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&example))));
+    let input = S::parse(&example).expect("Examples are valid inputs");
+    group.bench_function("part_1", |b| b.iter(|| S::part_1(black_box(&input))));
+    group.bench_function("part_2", |b| b.iter(|| S::part_2(black_box(&input))));
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    // Every event is enabled, but discarded: hot paths are only instrumented in benchmarks built
+    // with the `trace` feature, so comparing with a run without it shows the cost of instrumentation
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_writer(std::io::sink)
+        .init();
+    bench_solution::<day_1::Day1>(c, "day_1", include_str!("../src/day_1/example.txt"));
+    bench_solution::<day_2::Day2>(c, "day_2", include_str!("../src/day_2/example.txt"));
+    bench_solution::<day_3::Day3>(c, "day_3", include_str!("../src/day_3/example.txt"));
+    bench_solution::<day_4::Day4>(c, "day_4", include_str!("../src/day_4/example.txt"));
+    bench_solution::<day_5::Day5>(c, "day_5", include_str!("../src/day_5/example.txt"));
+    bench_solution::<day_6::Day6>(c, "day_6", include_str!("../src/day_6/example.txt"));
+    bench_solution::<day_7::Day7>(c, "day_7", include_str!("../src/day_7/example.txt"));
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
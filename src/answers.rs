@@ -0,0 +1,167 @@
+//! Known-good answers, used to verify that the solutions still produce them after a refactor.
+
+use crate::Error;
+use crate::error::IoAction;
+use crate::runner::{Answer, Part};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Answers recorded for a single day. Both strings and integers are accepted as answers.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    part_1: Option<toml::Value>,
+    part_2: Option<toml::Value>,
+}
+
+/// Known-good answers, loaded from a TOML file with a table for each day, e.g.:
+/// ```toml
+/// [day_1]
+/// part_1 = 1234
+/// part_2 = "5678"
+/// ```
+/// Days and parts that are missing from the file are considered unknown, but any other table or
+/// key is rejected, so that a typo can't turn a known answer into an unknown one.
+#[derive(Debug, Default)]
+pub struct KnownAnswers {
+    days: BTreeMap<Day, DayAnswers>,
+}
+
+/// Name of the table of a day, e.g. `day_1`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Deserialize)]
+#[serde(try_from = "String")]
+struct Day(u8);
+
+impl TryFrom<String> for Day {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.strip_prefix("day_")
+            .and_then(|x| x.parse().ok())
+            .map(Day)
+            .ok_or_else(|| format!("unknown table `{s}`, expected `day_N`"))
+    }
+}
+
+/// Outcome of comparing an answer with the known-good one.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Pass,
+    Fail,
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail => write!(f, "FAIL"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl KnownAnswers {
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        Ok(Self {
+            days: toml::from_str(s).map_err(Error::Answers)?,
+        })
+    }
+
+    /// Loads the known answers from a file, which must exist: checking against a missing file
+    /// would report every answer as unknown and pass.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let s = std::fs::read_to_string(path).map_err(|e| Error::io(IoAction::Read, path, e))?;
+        Self::parse(&s)
+    }
+
+    /// Returns the known-good answer to a part of the puzzle of the given day, if there's one.
+    pub fn get(&self, day: u8, part: Part) -> Option<String> {
+        let answers = self.days.get(&Day(day))?;
+        let answer = match part {
+            Part::One => answers.part_1.as_ref(),
+            Part::Two => answers.part_2.as_ref(),
+        }?;
+        Some(match answer {
+            toml::Value::String(s) => s.clone(),
+            value => value.to_string(),
+        })
+    }
+
+    pub fn check(&self, answer: &Answer) -> Verdict {
+        match self.get(answer.day, answer.part) {
+            Some(expected) if expected == answer.value => Verdict::Pass,
+            Some(_) => Verdict::Fail,
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn answer(day: u8, part: Part, value: &str) -> Answer {
+        Answer {
+            day,
+            part,
+            label: "",
+            value: value.to_string(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let known = KnownAnswers::parse(
+            r#"
+            [day_1]
+            part_1 = 3
+            part_2 = "6"
+
+            [day_2]
+            part_1 = 1227775554
+            "#,
+        )
+        .unwrap();
+        assert_eq!(known.check(&answer(1, Part::One, "3")), Verdict::Pass);
+        assert_eq!(known.check(&answer(1, Part::Two, "6")), Verdict::Pass);
+        assert_eq!(known.check(&answer(2, Part::One, "1")), Verdict::Fail);
+        assert_eq!(known.check(&answer(2, Part::Two, "1")), Verdict::Unknown);
+        assert_eq!(known.check(&answer(3, Part::One, "1")), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_invalid_answers_file() {
+        assert!(matches!(
+            KnownAnswers::parse("[day_1]\npart_1 = "),
+            Err(Error::Answers(_))
+        ));
+        // Typos are rejected rather than leaving the answers unknown
+        for typo in [
+            "[1]\n1 = \"3\"",
+            "[day_1]\n1 = \"3\"",
+            "[day_x]\npart_1 = 3",
+        ] {
+            assert!(
+                matches!(KnownAnswers::parse(typo), Err(Error::Answers(_))),
+                "{typo:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_missing_answers_file() {
+        assert!(matches!(
+            KnownAnswers::load(Path::new("this/file/does/not/exist.toml")),
+            Err(Error::Io {
+                action: IoAction::Read,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,436 @@
+//! Client for the Advent of Code website, used to download puzzle inputs and submit answers.
+
+use crate::Error;
+use crate::error::IoAction;
+use crate::registry::YEAR;
+use crate::runner::Part;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, info};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings of the client, read from a TOML file such as:
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "https://adventofcode.com"
+/// ```
+/// The `AOC_SESSION` and `AOC_BASE_URL` environment variables take precedence over the file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientConfig {
+    /// Value of the `session` cookie of a logged-in browser
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+}
+
+impl ClientConfig {
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        toml::from_str(s).map_err(Error::Config)
+    }
+
+    /// Loads the configuration from a file, then applies the environment variables on top of it.
+    /// A missing file is treated as an empty one.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let config = match std::fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(Error::io(IoAction::Read, path, e)),
+        };
+        Ok(Self {
+            session: std::env::var("AOC_SESSION").ok().or(config.session),
+            base_url: std::env::var("AOC_BASE_URL").ok().or(config.base_url),
+        })
+    }
+}
+
+/// How the website judged a submitted answer.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "outcome")]
+pub enum Outcome {
+    Correct,
+    /// The answer is wrong and too high. Like other wrong answers, it comes with a delay before
+    /// the next answer can be submitted
+    TooHigh {
+        #[serde(default)]
+        wait_seconds: u64,
+    },
+    TooLow {
+        #[serde(default)]
+        wait_seconds: u64,
+    },
+    /// The answer is wrong, without any hint about its magnitude
+    Wrong {
+        #[serde(default)]
+        wait_seconds: u64,
+    },
+    /// The answer wasn't judged, because the previous one was submitted too recently
+    RateLimited {
+        wait_seconds: u64,
+    },
+    /// The answer wasn't judged, because this part has already been solved
+    AlreadySolved,
+    /// The response didn't contain any of the known messages
+    Unrecognized,
+}
+
+impl Outcome {
+    /// Extracts the outcome of a submission from the page the website responds with.
+    pub fn from_response(page: &str) -> Self {
+        let wait_seconds = || parse_retry(page).unwrap_or_default().as_secs();
+        if page.contains("That's the right answer") {
+            Outcome::Correct
+        } else if page.contains("your answer is too high") {
+            Outcome::TooHigh {
+                wait_seconds: wait_seconds(),
+            }
+        } else if page.contains("your answer is too low") {
+            Outcome::TooLow {
+                wait_seconds: wait_seconds(),
+            }
+        } else if page.contains("That's not the right answer") {
+            Outcome::Wrong {
+                wait_seconds: wait_seconds(),
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Outcome::RateLimited {
+                wait_seconds: parse_wait(page).unwrap_or_default().as_secs(),
+            }
+        } else if page.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unrecognized
+        }
+    }
+
+    /// Whether the answer was judged to be wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Outcome::TooHigh { .. } | Outcome::TooLow { .. } | Outcome::Wrong { .. }
+        )
+    }
+
+    /// How long the website asked to wait before submitting another answer.
+    pub fn wait(&self) -> Duration {
+        match self {
+            Outcome::TooHigh { wait_seconds }
+            | Outcome::TooLow { wait_seconds }
+            | Outcome::Wrong { wait_seconds }
+            | Outcome::RateLimited { wait_seconds } => Duration::from_secs(*wait_seconds),
+            Outcome::Correct | Outcome::AlreadySolved | Outcome::Unrecognized => Duration::ZERO,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh { .. } => write!(f, "wrong, too high"),
+            Outcome::TooLow { .. } => write!(f, "wrong, too low"),
+            Outcome::Wrong { .. } => write!(f, "wrong"),
+            Outcome::RateLimited { wait_seconds } => {
+                write!(
+                    f,
+                    "not judged, wait {wait_seconds}s before submitting again"
+                )
+            }
+            Outcome::AlreadySolved => write!(f, "not judged, the part is already solved"),
+            Outcome::Unrecognized => write!(f, "not recognized"),
+        }
+    }
+}
+
+/// Parses the time left to wait out of a message such as "You have 1m 23s left to wait."
+fn parse_wait(page: &str) -> Option<Duration> {
+    let (_, rest) = page.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value = value.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Parses the delay before the next submission out of the message following a wrong answer, such as
+/// "Please wait one minute before trying again."
+fn parse_retry(page: &str) -> Option<Duration> {
+    let (message, _) = page.split_once(" before trying again")?;
+    let (_, wait) = message.rsplit_once("wait ")?;
+    let (value, unit) = wait.split_once(' ')?;
+    let value = match value {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        _ => value.parse::<u64>().ok()?,
+    };
+    let unit = match unit.trim_end_matches('s') {
+        "second" => 1,
+        "minute" => 60,
+        "hour" => 3600,
+        _ => return None,
+    };
+    Some(Duration::from_secs(value * unit))
+}
+
+#[derive(Debug)]
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            agent: ureq::Agent::new_with_defaults(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    pub fn from_config(config: &ClientConfig) -> Result<Self, Error> {
+        let session = config.session.as_deref().ok_or(Error::MissingSession)?;
+        Ok(Self::new(
+            config.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL),
+            session,
+        ))
+    }
+
+    fn url(&self, day: u8, path: &str) -> String {
+        format!("{}/{YEAR}/day/{day}{path}", self.base_url)
+    }
+
+    /// Downloads the puzzle input of the given day.
+    pub fn fetch_input(&self, day: u8) -> Result<String, Error> {
+        let url = self.url(day, "/input");
+        info!(%url, "fetching puzzle input");
+        Ok(self
+            .agent
+            .get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", concat!("aoc2025/", env!("CARGO_PKG_VERSION")))
+            .call()?
+            .body_mut()
+            .read_to_string()?)
+    }
+
+    /// Submits the answer to a part of the puzzle of the given day.
+    pub fn submit_answer(&self, day: u8, part: Part, answer: &str) -> Result<Outcome, Error> {
+        let url = self.url(day, "/answer");
+        info!(%url, %part, answer, "submitting answer");
+        let page = self
+            .agent
+            .post(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", concat!("aoc2025/", env!("CARGO_PKG_VERSION")))
+            .send_form([
+                ("level", u8::from(part).to_string().as_str()),
+                ("answer", answer),
+            ])?
+            .body_mut()
+            .read_to_string()?;
+        let outcome = Outcome::from_response(&page);
+        if outcome == Outcome::Unrecognized {
+            debug!(page, "unrecognized response to a submission");
+        }
+        Ok(outcome)
+    }
+
+    /// Makes sure the puzzle input of the given day is cached at the given path, downloading it
+    /// only if the file doesn't exist yet. Returns whether the input had to be downloaded.
+    pub fn fetch_input_to(&self, day: u8, path: &Path) -> Result<bool, Error> {
+        if path.exists() {
+            return Ok(false);
+        }
+        let input = self.fetch_input(day)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| Error::io(IoAction::CreateDir, dir, e))?;
+        }
+        std::fs::write(path, input).map_err(|e| Error::io(IoAction::Write, path, e))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+    use test_log::test;
+
+    /// Request received by the stub server.
+    #[derive(Debug)]
+    struct Request {
+        request_line: String,
+        headers: Vec<String>,
+        body: String,
+    }
+
+    /// Starts a server answering a single request with the given status and body, returning its
+    /// base URL and a handle resolving to the request it received.
+    fn stub_server(status: u16, body: &'static str) -> (String, JoinHandle<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut lines = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end().to_string();
+                if line.is_empty() {
+                    break;
+                }
+                lines.push(line);
+            }
+            let length = lines
+                .iter()
+                .find_map(|x| {
+                    x.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|x| x.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or_default();
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body).unwrap();
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            Request {
+                request_line: lines.remove(0),
+                headers: lines,
+                body: String::from_utf8(request_body).unwrap(),
+            }
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let (url, server) = stub_server(200, "L68\nL30\n");
+        let client = Client::new(&url, "secret");
+        assert_eq!(client.fetch_input(1).unwrap(), "L68\nL30\n");
+        let request = server.join().unwrap();
+        assert_eq!(request.request_line, "GET /2025/day/1/input HTTP/1.1");
+        assert_eq!(request.body, "");
+        assert!(
+            request
+                .headers
+                .iter()
+                .any(|x| x.eq_ignore_ascii_case("cookie: session=secret"))
+        );
+    }
+
+    #[test]
+    fn test_fetch_input_failure() {
+        let (url, server) = stub_server(404, "Please don't repeatedly request this endpoint");
+        let client = Client::new(&url, "secret");
+        assert!(matches!(
+            client.fetch_input(12),
+            Err(Error::Http(ureq::Error::StatusCode(404)))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_cached_input_is_not_refetched() {
+        let dir = std::env::temp_dir().join(format!("aoc2025-fetch-{}", std::process::id()));
+        let path = dir.join("day_3.txt");
+        let (url, server) = stub_server(200, "987654321111111\n");
+        let client = Client::new(&url, "secret");
+        assert!(client.fetch_input_to(3, &path).unwrap());
+        server.join().unwrap();
+        // Nothing listens anymore, so a second request would fail
+        assert!(!client.fetch_input_to(3, &path).unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "987654321111111\n");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_submit_answer() {
+        let (url, server) = stub_server(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high. \
+            Please wait one minute before trying again.</p></article></main>",
+        );
+        let client = Client::new(&url, "secret");
+        assert_eq!(
+            client.submit_answer(5, Part::Two, "1234").unwrap(),
+            Outcome::TooHigh { wait_seconds: 60 }
+        );
+        let request = server.join().unwrap();
+        assert_eq!(request.request_line, "POST /2025/day/5/answer HTTP/1.1");
+        assert_eq!(request.body, "level=2&answer=1234");
+    }
+
+    #[test]
+    fn test_outcome_from_response() {
+        assert_eq!(
+            Outcome::from_response("<p>That's the right answer! You are one gold star closer.</p>"),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::from_response(
+                "<p>That's not the right answer; your answer is too low.  Please wait 5 minutes \
+                before trying again.</p>"
+            ),
+            Outcome::TooLow { wait_seconds: 300 }
+        );
+        assert_eq!(
+            Outcome::from_response("<p>That's not the right answer. If you're stuck, ...</p>"),
+            Outcome::Wrong { wait_seconds: 0 }
+        );
+        assert_eq!(
+            Outcome::from_response(
+                "<p>You gave an answer too recently; you have to wait after submitting an answer \
+                before trying again.  You have 1m 23s left to wait.</p>"
+            ),
+            Outcome::RateLimited { wait_seconds: 83 }
+        );
+        assert_eq!(
+            Outcome::from_response(
+                "<p>You don't seem to be solving the right level.  Did you already complete it?</p>"
+            ),
+            Outcome::AlreadySolved
+        );
+        assert_eq!(
+            Outcome::from_response("<p>Something else</p>"),
+            Outcome::Unrecognized
+        );
+    }
+
+    #[test]
+    fn test_config() {
+        let config =
+            ClientConfig::parse("session = \"secret\"\nbase_url = \"http://localhost\"").unwrap();
+        let client = Client::from_config(&config).unwrap();
+        assert_eq!(client.url(4, "/input"), "http://localhost/2025/day/4/input");
+        assert!(matches!(
+            Client::from_config(&ClientConfig::default()),
+            Err(Error::MissingSession)
+        ));
+        assert!(matches!(
+            ClientConfig::parse("token = \"secret\""),
+            Err(Error::Config(_))
+        ));
+    }
+}
@@ -1,27 +1,108 @@
 mod dial;
+mod history;
+mod index;
+mod policy;
 mod rotation;
 
-use crate::day_1::dial::{Dial, PasswordMethod};
-use crate::day_1::rotation::Rotation;
-use std::io::Lines;
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::{Error, Solution};
 use tracing::instrument;
 
-#[instrument(skip(input))]
-pub fn day_1_solution(input: Lines<impl std::io::BufRead>) {
-    let rotations = input
-        .map_while(Result::ok)
-        .map(|s| Rotation::from(s.as_str()))
-        .collect::<Vec<_>>();
-    let mut dial = Dial::default();
-    for rotation in rotations {
+pub use crate::day_1::dial::{Dial, DialConfig};
+pub use crate::day_1::history::{RotationRecord, write_csv, write_json, write_json_lines};
+pub use crate::day_1::index::PasswordIndex;
+pub use crate::day_1::policy::{Method0x434C49434B, PasswordPolicy, Simple};
+pub use crate::day_1::rotation::{Rotation, RotationDirection};
+
+pub struct Day1;
+
+/// Replays the rotations of the input on a dial starting from its default position, recording what
+/// each of them did to it.
+pub fn rotation_history(input: &Input) -> Result<Vec<RotationRecord>, Error> {
+    let mut dial = Dial::default().with_history();
+    for rotation in Day1::parse(input)? {
         dial.rotate(rotation);
     }
-    println!(
-        "Simple password: {}",
-        dial.get_password(PasswordMethod::Simple)
-    );
-    println!(
-        "Password calculated with method 0x434C49434B: {}",
-        dial.get_password(PasswordMethod::Method0x434C49434B)
-    );
+    Ok(dial.history().to_vec())
+}
+
+impl Solution for Day1 {
+    type Input = Vec<Rotation>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    const TITLE: &'static str = "Secret Entrance";
+    const PART_1_LABEL: &'static str = "Simple password";
+    const PART_2_LABEL: &'static str = "Password calculated with method 0x434C49434B";
+
+    #[instrument(skip(input))]
+    fn parse(input: &Input) -> Result<Self::Input, Error> {
+        input
+            .lines()
+            .map(|line| Ok(line.parse::<Rotation>()?))
+            .collect()
+    }
+
+    fn validate(input: &Input) -> Vec<ParseError> {
+        input
+            .lines()
+            .filter_map(|line| line.parse::<Rotation>().err())
+            .collect()
+    }
+
+    fn part_1(rotations: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(Dial::default().password(rotations, &Simple))
+    }
+
+    fn part_2(rotations: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(Dial::default().password(rotations, &Method0x434C49434B))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    const EXAMPLE: &str = include_str!("day_1/example.txt");
+
+    #[test]
+    fn test_example() {
+        let rotations = Day1::parse(&Input::new(EXAMPLE)).unwrap();
+        assert_eq!(Day1::part_1(&rotations).unwrap(), 3);
+        assert_eq!(Day1::part_2(&rotations).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_invalid_rotation_line() {
+        let error = Day1::parse(&Input::new("L68\nL30\nU48")).unwrap_err();
+        assert!(matches!(error, Error::Parse(e) if e.line == 3 && e.column == 1));
+    }
+
+    #[test]
+    fn test_validate() {
+        let violations = Day1::validate(&Input::new("L68\nX30\nR\nR14\nLx"));
+        assert_eq!(
+            violations.iter().map(|x| x.line).collect::<Vec<_>>(),
+            vec![2, 3, 5]
+        );
+        assert!(Day1::validate(&Input::new(EXAMPLE)).is_empty());
+    }
+
+    #[test]
+    fn test_rotation_history() {
+        let history = rotation_history(&Input::new(EXAMPLE)).unwrap();
+        let rotations = Day1::parse(&Input::new(EXAMPLE)).unwrap();
+        assert_eq!(history.len(), rotations.len());
+        assert_eq!(history.iter().filter(|x| x.landed).count(), 3);
+        assert_eq!(
+            history
+                .iter()
+                .map(|x| x.crossings + u32::from(x.landed))
+                .sum::<u32>(),
+            6
+        );
+        assert!(history.windows(2).all(|x| x[0].end == x[1].start));
+    }
 }
@@ -1,206 +1,484 @@
+use crate::day_1::history::RotationRecord;
+use crate::day_1::policy::PasswordPolicy;
 use crate::day_1::rotation::{Rotation, RotationDirection};
-use tracing::{debug, info, instrument};
+use crate::trace::info;
 
-pub enum PasswordMethod {
-    Simple,
-    Method0x434C49434B,
+/// Describes the shape of a dial: how many positions it has, the one it starts from, and the mark
+/// whose hits make up the password.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct DialConfig {
+    pub positions: u32,
+    pub start: u32,
+    pub mark: u32,
 }
 
+impl Default for DialConfig {
+    fn default() -> Self {
+        Self {
+            positions: 100,
+            start: 50,
+            mark: 0,
+        }
+    }
+}
+
+impl DialConfig {
+    /// Counts the clicks of a rotation from the given position that point the dial at the target,
+    /// including the last one.
+    /// The first of them comes after as many clicks as the distance to the target in the direction
+    /// of the rotation, or after a full turn if the dial already points at it, and then one comes
+    /// after every full turn.
+    pub fn clicks_on(&self, from: u32, rotation: &Rotation, target: u32) -> u32 {
+        let size = i64::from(self.positions);
+        // Distance from the target to the position, turning the dial right
+        let offset = (i64::from(from) - i64::from(target)).rem_euclid(size);
+        let distance = match rotation.direction {
+            RotationDirection::Left => offset,
+            RotationDirection::Right => (size - offset) % size,
+        };
+        let first_click = if distance == 0 { size } else { distance };
+        let steps = i64::from(rotation.steps);
+        if steps < first_click {
+            0
+        } else {
+            ((steps - first_click) / size + 1) as u32
+        }
+    }
+}
+
+/// A dial, reporting what each rotation does to it so that a [`PasswordPolicy`] can score it.
 pub struct Dial {
-    position: i32,
-    point_at_zero_counter: u32,
-    traversed_zero_counter: u32,
+    config: DialConfig,
+    position: u32,
+    /// Every rotation so far, when they're being recorded
+    history: Option<Vec<RotationRecord>>,
 }
 
 impl Default for Dial {
     fn default() -> Self {
-        Self {
-            position: 50,
-            point_at_zero_counter: 0,
-            traversed_zero_counter: 0,
-        }
+        Self::new(DialConfig::default())
     }
 }
 
 impl Dial {
-    fn update_position(&mut self, rotation: &Rotation) -> bool {
-        let wrapped_steps = rotation.steps % 100;
-        match rotation.direction {
-            RotationDirection::Left => self.position -= wrapped_steps as i32,
-            RotationDirection::Right => self.position += wrapped_steps as i32,
+    /// Builds a dial pointing at its start position.
+    /// Panics if the dial has no positions, or if the start or mark are not among them.
+    pub fn new(config: DialConfig) -> Self {
+        assert!(
+            config.start < config.positions && config.mark < config.positions,
+            "The start ({}) and mark ({}) must be positions of the dial (0 to {})",
+            config.start,
+            config.mark,
+            config.positions.saturating_sub(1)
+        );
+        Self {
+            config,
+            position: config.start,
+            history: None,
         }
-        let overflow = self.position < 0 || self.position > 100;
-        self.position = self.position.rem_euclid(100);
-        overflow
     }
 
-    fn update_zero_reached_counter(&mut self) -> u32 {
-        let reached_zero = self.position == 0;
-        debug!(%reached_zero, "updating zero reached counter");
-        if reached_zero {
-            self.point_at_zero_counter += 1;
-        }
-        reached_zero as u32
-    }
-    fn update_zero_crossings_counter(
-        &mut self,
-        rotation: &Rotation,
-        overflow: bool,
-        last_position: i32,
-    ) -> u32 {
-        let traversed_zero = overflow && last_position != 0;
-        let mut full_cycles = rotation.steps / 100;
-        if full_cycles > 0 && last_position == 0 && self.position == 0 {
-            // Special case: we were at zero, we did N full rotations ending up at zero again - we should not consider this as a crossing
-            full_cycles -= 1;
-        }
-        if traversed_zero {
-            full_cycles += 1;
+    /// Records what every following rotation does to the dial, see [`Dial::history`].
+    pub fn with_history(self) -> Self {
+        Self {
+            history: Some(vec![]),
+            ..self
         }
-        debug!(%traversed_zero, %full_cycles, %last_position, "updating zero crossings counter");
-        self.traversed_zero_counter += full_cycles;
-        full_cycles
     }
 
-    #[instrument(skip(self))]
-    pub fn rotate(&mut self, rotation: Rotation) {
+    /// Returns the records of the rotations so far, or an empty history if they're not being recorded.
+    pub fn history(&self) -> &[RotationRecord] {
+        self.history.as_deref().unwrap_or_default()
+    }
+
+    pub fn config(&self) -> &DialConfig {
+        &self.config
+    }
+
+    pub fn position(&self) -> u32 {
+        self.position
+    }
+
+    fn update_position(&mut self, rotation: &Rotation) {
+        let size = i64::from(self.config.positions);
+        let wrapped_steps = i64::from(rotation.steps) % size;
+        let steps = match rotation.direction {
+            RotationDirection::Left => -wrapped_steps,
+            RotationDirection::Right => wrapped_steps,
+        };
+        self.position = (i64::from(self.position) + steps).rem_euclid(size) as u32;
+    }
+
+    /// Rotates the dial, returning what the rotation did to it.
+    #[cfg_attr(
+        any(debug_assertions, feature = "trace"),
+        tracing::instrument(skip(self))
+    )]
+    pub fn rotate(&mut self, rotation: Rotation) -> RotationRecord {
         let last_position = self.position;
-        let overflow = self.update_position(&rotation);
-        let new_zero_reached_counts = self.update_zero_reached_counter();
-        let new_zero_crossing_counts =
-            self.update_zero_crossings_counter(&rotation, overflow, last_position);
-        info!(%new_zero_reached_counts, %new_zero_crossing_counts, %last_position, %self.position, "Dial state after rotation");
-    }
-
-    pub fn get_password(&self, password_method: PasswordMethod) -> u32 {
-        match password_method {
-            PasswordMethod::Simple => self.point_at_zero_counter,
-            PasswordMethod::Method0x434C49434B => {
-                self.traversed_zero_counter + self.point_at_zero_counter
-            }
+        let mark_clicks = self
+            .config
+            .clicks_on(last_position, &rotation, self.config.mark);
+        self.update_position(&rotation);
+        let landed = self.position == self.config.mark;
+        let record = RotationRecord {
+            rotation,
+            start: last_position,
+            end: self.position,
+            landed,
+            // The last click is the one landing on the mark, if the dial moved at all
+            crossings: mark_clicks - u32::from(landed && rotation.steps > 0),
+        };
+        if let Some(history) = &mut self.history {
+            history.push(record);
         }
+        info!(%landed, %mark_clicks, %last_position, %self.position, "Dial state after rotation");
+        record
+    }
+
+    /// Rotates the dial, returning the points the policy gives to the rotation.
+    pub fn rotate_with(&mut self, rotation: Rotation, policy: &impl PasswordPolicy) -> u32 {
+        let record = self.rotate(rotation);
+        policy.points(&self.config, &record)
+    }
+
+    /// Rotates the dial through all the rotations, returning the password the policy derives from them.
+    pub fn password(&mut self, rotations: &[Rotation], policy: &impl PasswordPolicy) -> u32 {
+        rotations.iter().map(|x| self.rotate_with(*x, policy)).sum()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::day_1::policy::{Method0x434C49434B, Simple};
+    use proptest::prelude::*;
     use test_log::test;
 
+    /// Naive dial turning one click at a time, which the counts of [`Dial`] are checked against.
+    struct ReferenceDial {
+        config: DialConfig,
+        position: u32,
+        landings: u32,
+        clicks: u32,
+    }
+
+    impl ReferenceDial {
+        fn new(config: DialConfig) -> Self {
+            Self {
+                config,
+                position: config.start,
+                landings: 0,
+                clicks: 0,
+            }
+        }
+
+        fn rotate(&mut self, rotation: Rotation) {
+            let positions = self.config.positions;
+            for _ in 0..rotation.steps {
+                self.position = match rotation.direction {
+                    RotationDirection::Left => (self.position + positions - 1) % positions,
+                    RotationDirection::Right => (self.position + 1) % positions,
+                };
+                self.clicks += u32::from(self.position == self.config.mark);
+            }
+            self.landings += u32::from(self.position == self.config.mark);
+        }
+    }
+
+    /// Dials of any size, with rotations going around them a few times at most.
+    fn dial_and_rotations() -> impl Strategy<Value = (DialConfig, Vec<Rotation>)> {
+        (1..=150_u32).prop_flat_map(|positions| {
+            let config = (0..positions, 0..positions).prop_map(move |(start, mark)| DialConfig {
+                positions,
+                start,
+                mark,
+            });
+            (config, rotations(3 * positions))
+        })
+    }
+
+    fn rotations(max_steps: u32) -> impl Strategy<Value = Vec<Rotation>> {
+        let rotation = (any::<bool>(), 0..=max_steps).prop_map(|(left, steps)| Rotation {
+            direction: if left {
+                RotationDirection::Left
+            } else {
+                RotationDirection::Right
+            },
+            steps,
+        });
+        prop::collection::vec(rotation, 0..40)
+    }
+
+    fn assert_matches_reference(
+        config: DialConfig,
+        rotations: &[Rotation],
+    ) -> Result<(), TestCaseError> {
+        let mut reference = ReferenceDial::new(config);
+        for rotation in rotations {
+            reference.rotate(*rotation);
+        }
+        let mut dial = Dial::new(config);
+        prop_assert_eq!(dial.password(rotations, &Simple), reference.landings);
+        prop_assert_eq!(dial.position, reference.position);
+        prop_assert_eq!(
+            Dial::new(config).password(rotations, &Method0x434C49434B),
+            reference.clicks
+        );
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_reference_dial((config, rotations) in dial_and_rotations()) {
+            assert_matches_reference(config, &rotations)?;
+        }
+
+        #[test]
+        fn test_matches_reference_dial_with_puzzle_rotations(rotations in rotations(1000)) {
+            assert_matches_reference(DialConfig::default(), &rotations)?;
+        }
+    }
+
     #[test]
     fn test_simple_rotations() {
         let mut dial = Dial::default();
-        dial.rotate(Rotation {
-            direction: RotationDirection::Left,
-            steps: 3,
-        });
+        let mut password = dial.rotate_with(
+            Rotation {
+                direction: RotationDirection::Left,
+                steps: 3,
+            },
+            &Simple,
+        );
         assert_eq!(dial.position, 47);
-        dial.rotate(Rotation {
-            direction: RotationDirection::Right,
-            steps: 4,
-        });
+        password += dial.rotate_with(
+            Rotation {
+                direction: RotationDirection::Right,
+                steps: 4,
+            },
+            &Simple,
+        );
         assert_eq!(dial.position, 51);
         // Ensure the counter is incremented correctly
-        dial.rotate(Rotation {
-            direction: RotationDirection::Right,
-            steps: 49,
-        });
-        assert_eq!(dial.point_at_zero_counter, 1);
-        dial.rotate(Rotation {
-            direction: RotationDirection::Left,
-            steps: 50,
-        });
-        dial.rotate(Rotation {
-            direction: RotationDirection::Left,
-            steps: 50,
-        });
-        assert_eq!(dial.point_at_zero_counter, 2);
-        // The password for the simple case should be equal to the number of times the dial has reached zero
-        assert_eq!(dial.get_password(PasswordMethod::Simple), 2)
+        password += dial.rotate_with(
+            Rotation {
+                direction: RotationDirection::Right,
+                steps: 49,
+            },
+            &Simple,
+        );
+        assert_eq!(password, 1);
+        password += dial.password(
+            &[
+                Rotation {
+                    direction: RotationDirection::Left,
+                    steps: 50,
+                },
+                Rotation {
+                    direction: RotationDirection::Left,
+                    steps: 50,
+                },
+            ],
+            &Simple,
+        );
+        assert_eq!(password, 2);
     }
 
     /// Ensure that the dial doesn't move when rotating by multiples of 100
     #[test]
     fn test_long_rotations() {
+        let rotations = [
+            Rotation {
+                direction: RotationDirection::Left,
+                steps: 300,
+            },
+            Rotation {
+                direction: RotationDirection::Right,
+                steps: 500,
+            },
+        ];
         let mut dial = Dial::default();
-        dial.rotate(Rotation {
-            direction: RotationDirection::Left,
-            steps: 300,
-        });
+        assert_eq!(dial.rotate_with(rotations[0], &Method0x434C49434B), 3);
         assert_eq!(dial.position, 50);
-        assert_eq!(dial.get_password(PasswordMethod::Simple), 0);
-        assert_eq!(dial.get_password(PasswordMethod::Method0x434C49434B), 3);
-        dial.rotate(Rotation {
-            direction: RotationDirection::Right,
-            steps: 500,
-        });
+        assert_eq!(dial.rotate_with(rotations[1], &Method0x434C49434B), 5);
         assert_eq!(dial.position, 50);
-        assert_eq!(dial.get_password(PasswordMethod::Simple), 0);
-        assert_eq!(dial.get_password(PasswordMethod::Method0x434C49434B), 8);
+        assert_eq!(Dial::default().password(&rotations, &Simple), 0);
     }
 
     #[test]
     fn test_method_0x434c49434b() {
         let mut dial = Dial::default();
-        dial.rotate(Rotation {
-            direction: RotationDirection::Right,
-            steps: 60,
-        });
-        assert_eq!(dial.get_password(PasswordMethod::Method0x434C49434B), 1);
+        let password = dial.rotate_with(
+            Rotation {
+                direction: RotationDirection::Right,
+                steps: 60,
+            },
+            &Method0x434C49434B,
+        );
+        assert_eq!(password, 1);
 
         let mut dial = Dial::default();
-        dial.rotate(Rotation {
-            direction: RotationDirection::Left,
-            steps: 50,
-        });
+        let mut password = dial.rotate_with(
+            Rotation {
+                direction: RotationDirection::Left,
+                steps: 50,
+            },
+            &Method0x434C49434B,
+        );
         // We reached zero once, and we never crossed it
-        assert_eq!(dial.get_password(PasswordMethod::Method0x434C49434B), 1);
-        dial.rotate(Rotation {
-            direction: RotationDirection::Right,
-            steps: 30,
-        });
+        assert_eq!(password, 1);
+        password += dial.rotate_with(
+            Rotation {
+                direction: RotationDirection::Right,
+                steps: 30,
+            },
+            &Method0x434C49434B,
+        );
         // We did not cross zero, so the password should stay at 1
-        assert_eq!(dial.get_password(PasswordMethod::Method0x434C49434B), 1);
-        dial.rotate(Rotation {
-            direction: RotationDirection::Right,
-            steps: 80,
-        });
+        assert_eq!(password, 1);
+        password += dial.rotate_with(
+            Rotation {
+                direction: RotationDirection::Right,
+                steps: 80,
+            },
+            &Method0x434C49434B,
+        );
         // We crossed zero now, and we reached zero before, so the total should be 2
-        assert_eq!(dial.get_password(PasswordMethod::Method0x434C49434B), 2);
-        dial.rotate(Rotation {
-            direction: RotationDirection::Left,
-            steps: 300,
-        });
+        assert_eq!(password, 2);
+        password += dial.rotate_with(
+            Rotation {
+                direction: RotationDirection::Left,
+                steps: 300,
+            },
+            &Method0x434C49434B,
+        );
         // We crossed zero three times, so the counter should reflect that
-        assert_eq!(dial.get_password(PasswordMethod::Method0x434C49434B), 5);
+        assert_eq!(password, 5);
 
         let mut dial = Dial {
             position: 0,
             ..Default::default()
         };
         // Special case: we're at zero, and we need to travel right by 100 steps - the counter should only increment once
-        dial.rotate(Rotation {
-            direction: RotationDirection::Right,
-            steps: 100,
-        });
-        assert_eq!(dial.get_password(PasswordMethod::Method0x434C49434B), 1);
+        let password = dial.rotate_with(
+            Rotation {
+                direction: RotationDirection::Right,
+                steps: 100,
+            },
+            &Method0x434C49434B,
+        );
+        assert_eq!(password, 1);
     }
 
     #[test]
     fn test_method_0x434c49434b_full_cycle_reaching_zero() {
         // Special case: we're not at zero, but we perform N full rotations AND end up at zero again - both counters should increment accordingly
-        let mut dial = Dial::default();
-        dial.rotate(Rotation {
+        let password = Dial::default().rotate_with(
+            Rotation {
+                direction: RotationDirection::Right,
+                steps: 250,
+            },
+            &Method0x434C49434B,
+        );
+        assert_eq!(password, 3);
+
+        let password = Dial::default().rotate_with(
+            Rotation {
+                direction: RotationDirection::Left,
+                steps: 250,
+            },
+            &Method0x434C49434B,
+        );
+        assert_eq!(password, 3);
+    }
+
+    #[test]
+    fn test_custom_config() {
+        let config = DialConfig {
+            positions: 10,
+            start: 3,
+            mark: 7,
+        };
+        let right = |steps| Rotation {
             direction: RotationDirection::Right,
-            steps: 250,
+            steps,
+        };
+        let left = |steps| Rotation {
+            direction: RotationDirection::Left,
+            steps,
+        };
+        // Lands on the mark, then goes past it twice, ending at 2
+        let rotations = [right(4), left(25)];
+        let mut dial = Dial::new(config);
+        assert_eq!(dial.password(&rotations, &Simple), 1);
+        assert_eq!(dial.position, 2);
+        assert_eq!(
+            Dial::new(config).password(&rotations, &Method0x434C49434B),
+            3
+        );
+        // A dial with a single position points at its mark after every click
+        let config = DialConfig {
+            positions: 1,
+            start: 0,
+            mark: 0,
+        };
+        let rotations = [right(3), left(2)];
+        assert_eq!(Dial::new(config).password(&rotations, &Simple), 2);
+        assert_eq!(
+            Dial::new(config).password(&rotations, &Method0x434C49434B),
+            5
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must be positions of the dial")]
+    fn test_mark_outside_of_the_dial() {
+        Dial::new(DialConfig {
+            positions: 10,
+            start: 0,
+            mark: 10,
         });
-        assert_eq!(dial.get_password(PasswordMethod::Method0x434C49434B), 3);
+    }
 
+    #[test]
+    fn test_rotation_without_steps() {
+        // The dial is left pointing at the mark, but no click points it there
+        let config = DialConfig {
+            start: 0,
+            ..Default::default()
+        };
+        let rotation = Rotation {
+            direction: RotationDirection::Right,
+            steps: 0,
+        };
+        assert_eq!(Dial::new(config).rotate_with(rotation, &Simple), 1);
+        assert_eq!(
+            Dial::new(config).rotate_with(rotation, &Method0x434C49434B),
+            0
+        );
+    }
+
+    #[test]
+    fn test_history() {
+        let rotation = |direction, steps| Rotation { direction, steps };
         let mut dial = Dial::default();
-        dial.rotate(Rotation {
-            direction: RotationDirection::Left,
-            steps: 250,
-        });
-        assert_eq!(dial.get_password(PasswordMethod::Method0x434C49434B), 3);
+        dial.rotate(rotation(RotationDirection::Left, 10));
+        assert!(dial.history().is_empty());
+
+        let mut dial = Dial::default().with_history();
+        dial.rotate(rotation(RotationDirection::Left, 250));
+        dial.rotate(rotation(RotationDirection::Right, 120));
+        dial.rotate(rotation(RotationDirection::Right, 0));
+        assert_eq!(
+            dial.history()
+                .iter()
+                .map(|x| (x.start, x.end, x.landed, x.crossings))
+                .collect::<Vec<_>>(),
+            vec![(50, 0, true, 2), (0, 20, false, 1), (20, 20, false, 0)]
+        );
+        assert_eq!(
+            dial.history()[1].rotation,
+            rotation(RotationDirection::Right, 120)
+        );
     }
 }
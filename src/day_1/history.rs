@@ -0,0 +1,117 @@
+use crate::day_1::rotation::Rotation;
+use serde::{Serialize, Serializer};
+use std::io::Write;
+
+/// What a single rotation did to the dial.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub struct RotationRecord {
+    #[serde(serialize_with = "serialize_rotation")]
+    pub rotation: Rotation,
+    pub start: u32,
+    pub end: u32,
+    /// Whether the rotation left the dial pointing at the mark
+    pub landed: bool,
+    /// Clicks that pointed the dial at the mark before the end of the rotation
+    pub crossings: u32,
+}
+
+fn serialize_rotation<S: Serializer>(
+    rotation: &Rotation,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(rotation)
+}
+
+/// Writes the records as CSV, with a header row.
+pub fn write_csv(records: &[RotationRecord], mut writer: impl Write) -> std::io::Result<()> {
+    writeln!(writer, "rotation,start,end,landed,crossings")?;
+    for x in records {
+        writeln!(
+            writer,
+            "{},{},{},{},{}",
+            x.rotation, x.start, x.end, x.landed, x.crossings
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the records as a JSON array.
+pub fn write_json(records: &[RotationRecord], mut writer: impl Write) -> std::io::Result<()> {
+    serde_json::to_writer(&mut writer, records)?;
+    writeln!(writer)
+}
+
+/// Writes the records as JSON Lines, i.e. one JSON object per line.
+pub fn write_json_lines(records: &[RotationRecord], mut writer: impl Write) -> std::io::Result<()> {
+    for x in records {
+        serde_json::to_writer(&mut writer, x)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_1::rotation::RotationDirection;
+
+    const RECORDS: [RotationRecord; 2] = [
+        RotationRecord {
+            rotation: Rotation {
+                direction: RotationDirection::Left,
+                steps: 68,
+            },
+            start: 50,
+            end: 82,
+            landed: false,
+            crossings: 1,
+        },
+        RotationRecord {
+            rotation: Rotation {
+                direction: RotationDirection::Right,
+                steps: 18,
+            },
+            start: 82,
+            end: 0,
+            landed: true,
+            crossings: 0,
+        },
+    ];
+
+    #[test]
+    fn test_write_csv() {
+        let mut csv = vec![];
+        write_csv(&RECORDS, &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "rotation,start,end,landed,crossings\nL68,50,82,false,1\nR18,82,0,true,0\n"
+        );
+    }
+
+    #[test]
+    fn test_write_json() {
+        let mut json = vec![];
+        write_json(&RECORDS, &mut json).unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            concat!(
+                r#"[{"rotation":"L68","start":50,"end":82,"landed":false,"crossings":1},"#,
+                r#"{"rotation":"R18","start":82,"end":0,"landed":true,"crossings":0}]"#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_write_json_lines() {
+        let mut json = vec![];
+        write_json_lines(&RECORDS, &mut json).unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap().lines().collect::<Vec<_>>(),
+            [
+                r#"{"rotation":"L68","start":50,"end":82,"landed":false,"crossings":1}"#,
+                r#"{"rotation":"R18","start":82,"end":0,"landed":true,"crossings":0}"#
+            ]
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use crate::day_1::dial::{Dial, DialConfig};
+use crate::day_1::policy::PasswordPolicy;
+use crate::day_1::rotation::Rotation;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Answers the password that any contiguous slice of a list of rotations would give on its own,
+/// i.e. starting from the start position of the dial, without replaying the slice.
+///
+/// A slice starting at `i` behaves like a replay of the whole list whose dial is back at the start
+/// position after `i` rotations, so its password is the difference of two running passwords of
+/// that replay. The list is replayed once from each position such a replay starts from, of which
+/// there are at most as many as positions of the dial, and as rotations. Building the index thus
+/// takes up to `min(positions, rotations) * rotations` steps and passwords: fine for the dial of
+/// the puzzle, but quadratic in the amount of rotations for large dials.
+pub struct PasswordIndex {
+    config: DialConfig,
+    /// Distance the dial turned right after each prefix of the rotations
+    displacements: Vec<u32>,
+    /// Running passwords of the replays, by the position they start from
+    passwords: HashMap<u32, Vec<u32>>,
+}
+
+/// Position a replay of the whole list starts from to be back at the start position of the dial
+/// once it turned right by the given distance.
+fn replay_start(config: &DialConfig, displacement: u32) -> u32 {
+    (i64::from(config.start) - i64::from(displacement)).rem_euclid(i64::from(config.positions))
+        as u32
+}
+
+impl PasswordIndex {
+    pub fn new(config: DialConfig, rotations: &[Rotation], policy: &impl PasswordPolicy) -> Self {
+        let replay = |start| {
+            let mut dial = Dial::new(DialConfig { start, ..config });
+            let running = rotations.iter().scan(0, |password, rotation| {
+                let record = dial.rotate(*rotation);
+                // The policy sees the dial that was configured, whichever position the replay starts from
+                *password += policy.points(&config, &record);
+                Some((record.end, *password))
+            });
+            std::iter::once((start, 0))
+                .chain(running)
+                .collect::<Vec<_>>()
+        };
+        let displacements = replay(config.start)
+            .into_iter()
+            .map(|(x, _)| {
+                (i64::from(x) - i64::from(config.start)).rem_euclid(i64::from(config.positions))
+                    as u32
+            })
+            .collect::<Vec<_>>();
+        let mut passwords = HashMap::new();
+        for displacement in &displacements {
+            passwords
+                .entry(replay_start(&config, *displacement))
+                .or_insert_with_key(|start| replay(*start).into_iter().map(|(_, x)| x).collect());
+        }
+        Self {
+            config,
+            displacements,
+            passwords,
+        }
+    }
+
+    /// Amount of rotations that can be queried.
+    pub fn len(&self) -> usize {
+        self.displacements.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the password given by the rotations in the range alone.
+    /// Panics if the range is out of the bounds of the rotations, like slicing them would.
+    pub fn password(&self, range: Range<usize>) -> u32 {
+        assert!(
+            range.start <= range.end && range.end <= self.len(),
+            "Range {range:?} out of the bounds of {} rotations",
+            self.len()
+        );
+        let start = replay_start(&self.config, self.displacements[range.start]);
+        let passwords = &self.passwords[&start];
+        passwords[range.end] - passwords[range.start]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_1::policy::{Method0x434C49434B, Simple};
+    use crate::day_1::rotation::RotationDirection;
+    use proptest::prelude::*;
+
+    fn rotation() -> impl Strategy<Value = Rotation> {
+        (any::<bool>(), 0..=350_u32).prop_map(|(left, steps)| Rotation {
+            direction: if left {
+                RotationDirection::Left
+            } else {
+                RotationDirection::Right
+            },
+            steps,
+        })
+    }
+
+    fn assert_matches_replay(
+        config: DialConfig,
+        rotations: &[Rotation],
+        range: Range<usize>,
+        policy: &impl PasswordPolicy,
+    ) -> Result<(), TestCaseError> {
+        let index = PasswordIndex::new(config, rotations, policy);
+        prop_assert_eq!(
+            index.password(range.clone()),
+            Dial::new(config).password(&rotations[range], policy)
+        );
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_replaying_the_slice(
+            positions in prop_oneof![1..=120_u32, u32::MAX - 120..=u32::MAX],
+            start in any::<u32>(),
+            mark in any::<u32>(),
+            rotations in prop::collection::vec(rotation(), 0..30),
+            bounds in (any::<usize>(), any::<usize>()),
+        ) {
+            let config = DialConfig { positions, start: start % positions, mark: mark % positions };
+            let (a, b) = (bounds.0 % (rotations.len() + 1), bounds.1 % (rotations.len() + 1));
+            let range = a.min(b)..a.max(b);
+            assert_matches_replay(config, &rotations, range.clone(), &Simple)?;
+            assert_matches_replay(config, &rotations, range, &Method0x434C49434B)?;
+        }
+    }
+
+    #[test]
+    fn test_whole_list() {
+        let rotations = [
+            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
+        ]
+        .map(|x| x.parse::<Rotation>().unwrap());
+        let simple = PasswordIndex::new(DialConfig::default(), &rotations, &Simple);
+        let method_0x434c49434b =
+            PasswordIndex::new(DialConfig::default(), &rotations, &Method0x434C49434B);
+        assert_eq!(simple.len(), 10);
+        assert_eq!(simple.password(0..10), 3);
+        assert_eq!(method_0x434c49434b.password(0..10), 6);
+        // Starting from 50, L55 and L99 both go past 0 without landing on it
+        assert_eq!(simple.password(5..8), 0);
+        assert_eq!(method_0x434c49434b.password(5..8), 2);
+        assert_eq!(simple.password(4..4), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of the bounds")]
+    fn test_range_out_of_bounds() {
+        PasswordIndex::new(DialConfig::default(), &[], &Simple).password(0..1);
+    }
+}
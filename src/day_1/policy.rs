@@ -0,0 +1,139 @@
+use crate::day_1::dial::DialConfig;
+use crate::day_1::history::RotationRecord;
+
+/// A rule deriving a password from what the rotations do to a dial: where each of them starts
+/// and ends, whether it lands on the mark, and how many times it goes past it.
+///
+/// The password is the sum of the points given to every rotation on its own, which is what lets
+/// [`crate::day_1::PasswordIndex`] answer any slice of the rotations. Policies must therefore be
+/// stateless and additive: the points of a rotation may only depend on the configured dial and on
+/// the record of the rotation, not on the rotations that came before it.
+pub trait PasswordPolicy {
+    /// Points given to a rotation of the dial with the given configuration.
+    fn points(&self, config: &DialConfig, record: &RotationRecord) -> u32;
+}
+
+/// Counts the rotations that leave the dial pointing at the mark.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Simple;
+
+impl PasswordPolicy for Simple {
+    fn points(&self, _: &DialConfig, record: &RotationRecord) -> u32 {
+        u32::from(record.landed)
+    }
+}
+
+/// Counts the clicks that point the dial at the mark, during a rotation or at its end.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Method0x434C49434B;
+
+impl PasswordPolicy for Method0x434C49434B {
+    fn points(&self, _: &DialConfig, record: &RotationRecord) -> u32 {
+        // A rotation that doesn't move the dial doesn't click on the mark, even if it points at it
+        record.crossings + u32::from(record.landed && record.rotation.steps > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_1::dial::Dial;
+    use crate::day_1::index::PasswordIndex;
+    use crate::day_1::rotation::{Rotation, RotationDirection};
+    use test_log::test;
+
+    const EXAMPLE: [&str; 10] = [
+        "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
+    ];
+
+    fn rotations() -> [Rotation; 10] {
+        EXAMPLE.map(|x| x.parse::<Rotation>().unwrap())
+    }
+
+    fn password(config: DialConfig, policy: &impl PasswordPolicy) -> u32 {
+        Dial::new(config).password(&rotations(), policy)
+    }
+
+    /// Checks that the index gives the same password as replaying every slice of the example.
+    fn assert_index_matches_replay(config: DialConfig, policy: &impl PasswordPolicy) {
+        let rotations = rotations();
+        let index = PasswordIndex::new(config, &rotations, policy);
+        for start in 0..=rotations.len() {
+            for end in start..=rotations.len() {
+                assert_eq!(
+                    index.password(start..end),
+                    Dial::new(config).password(&rotations[start..end], policy),
+                    "{start}..{end}"
+                );
+            }
+        }
+    }
+
+    /// Counts every click pointing the dial at any of the positions.
+    struct Visits(Vec<u32>);
+
+    impl PasswordPolicy for Visits {
+        fn points(&self, config: &DialConfig, record: &RotationRecord) -> u32 {
+            self.0
+                .iter()
+                .map(|x| config.clicks_on(record.start, &record.rotation, *x))
+                .sum()
+        }
+    }
+
+    /// Counts the rotations coming back to the position the dial started from.
+    struct BackToStart;
+
+    impl PasswordPolicy for BackToStart {
+        fn points(&self, config: &DialConfig, record: &RotationRecord) -> u32 {
+            u32::from(record.end == config.start)
+        }
+    }
+
+    /// Counts the landings on the mark, weighted by the direction of the rotation.
+    struct ByDirection {
+        left: u32,
+        right: u32,
+    }
+
+    impl PasswordPolicy for ByDirection {
+        fn points(&self, config: &DialConfig, record: &RotationRecord) -> u32 {
+            let weight = match record.rotation.direction() {
+                RotationDirection::Left => self.left,
+                RotationDirection::Right => self.right,
+            };
+            weight * Simple.points(config, record)
+        }
+    }
+
+    #[test]
+    fn test_puzzle_policies() {
+        assert_eq!(password(DialConfig::default(), &Simple), 3);
+        assert_eq!(password(DialConfig::default(), &Method0x434C49434B), 6);
+    }
+
+    #[test]
+    fn test_custom_policies() {
+        let config = DialConfig::default();
+        // Visiting the mark alone is the same as method 0x434C49434B
+        assert_eq!(password(config, &Visits(vec![0])), 6);
+        // Every rotation but L30, L55, L99 and R14 goes by 99 once
+        assert_eq!(password(config, &Visits(vec![0, 99])), 12);
+        // R48 lands on the mark, then L55 and L99
+        let weighted = ByDirection { left: 1, right: 10 };
+        assert_eq!(password(config, &weighted), 12);
+    }
+
+    #[test]
+    fn test_index_matches_replay() {
+        let config = DialConfig {
+            positions: 100,
+            start: 20,
+            mark: 50,
+        };
+        assert_index_matches_replay(config, &Visits(vec![0, 50, 99]));
+        assert_index_matches_replay(config, &ByDirection { left: 1, right: 10 });
+        assert_index_matches_replay(config, &BackToStart);
+        assert_index_matches_replay(DialConfig::default(), &BackToStart);
+    }
+}
@@ -1,7 +1,10 @@
+use crate::error::{ParseError, ParseErrorKind};
+use crate::parse;
+use crate::trace::debug;
 use std::fmt::{Display, Formatter};
-use tracing::debug;
+use std::str::FromStr;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum RotationDirection {
     Left,
     Right,
@@ -16,24 +19,57 @@ impl Display for RotationDirection {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct Rotation {
     pub(crate) direction: RotationDirection,
     pub(crate) steps: u32,
 }
 
-impl From<&str> for Rotation {
-    fn from(s: &str) -> Self {
+impl Rotation {
+    pub fn direction(&self) -> RotationDirection {
+        self.direction
+    }
+
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+}
+
+impl FromStr for Rotation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         debug!(%s, "Parsing rotation");
-        let first_char = s.chars().next().unwrap();
-        Self {
-            direction: match first_char {
-                'L' => RotationDirection::Left,
-                'R' => RotationDirection::Right,
-                _ => panic!("Invalid rotation direction"),
-            },
-            steps: s[1..].parse().unwrap(),
+        let mut chars = s.chars();
+        let direction = match chars.next() {
+            Some('L') => RotationDirection::Left,
+            Some('R') => RotationDirection::Right,
+            Some(c) => {
+                return Err(ParseError::new(
+                    1,
+                    1,
+                    ParseErrorKind::UnexpectedChar {
+                        found: c,
+                        expected: "a rotation direction ('L' or 'R')",
+                    },
+                ));
+            }
+            None => return Err(ParseError::new(1, 1, ParseErrorKind::EmptyInput)),
+        };
+        let steps = chars.as_str();
+        if steps.is_empty() {
+            return Err(ParseError::new(
+                1,
+                2,
+                ParseErrorKind::UnexpectedEndOfLine {
+                    expected: "the number of steps",
+                },
+            ));
         }
+        Ok(Self {
+            direction,
+            steps: parse::number(steps, 2)?,
+        })
     }
 }
 
@@ -42,3 +78,28 @@ impl Display for Rotation {
         write!(f, "{}{}", self.direction, self.steps)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rotation() {
+        let rotation = "L68".parse::<Rotation>().unwrap();
+        assert!(matches!(rotation.direction, RotationDirection::Left));
+        assert_eq!(rotation.steps, 68);
+        assert_eq!(
+            "X68".parse::<Rotation>().unwrap_err(),
+            ParseError::new(
+                1,
+                1,
+                ParseErrorKind::UnexpectedChar {
+                    found: 'X',
+                    expected: "a rotation direction ('L' or 'R')"
+                }
+            )
+        );
+        assert_eq!("R".parse::<Rotation>().unwrap_err().column, 2);
+        assert_eq!("R1x".parse::<Rotation>().unwrap_err().column, 2);
+    }
+}
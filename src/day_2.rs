@@ -1,35 +1,138 @@
 mod id;
 
 use crate::day_2::id::{generate_invalid_ids_at_least_twice, generate_invalid_ids_exactly_twice};
-use std::io::Lines;
+use crate::error::{ParseError, ParseErrorKind};
+use crate::input::Input;
+use crate::{Error, Solution, parse};
 use tracing::instrument;
 
-#[instrument(skip(input))]
-pub fn day_2_solution(mut input: Lines<impl std::io::BufRead>) {
-    // The input will always be made of a single line representing a comma separated list of ranges
-    let input = input.next().unwrap().unwrap();
-    let input = input
-        .split(',')
-        .map(|s| {
-            let mut parts = s.split('-').map(|part| part.parse::<u64>().unwrap());
-            (parts.next().unwrap(), parts.next().unwrap())
-        })
-        .collect::<Vec<_>>();
-    let invalid_ids_exactly_2 = input
-        .iter()
-        .flat_map(|x| generate_invalid_ids_exactly_twice(*x))
-        .collect::<Vec<_>>();
-
-    println!(
-        "Sum of invalid IDs (with exactly 2 repeated subsequences): {:?}",
-        invalid_ids_exactly_2.iter().sum::<u64>()
-    );
-    let invalid_ids_at_least_2 = input
-        .iter()
-        .flat_map(|x| generate_invalid_ids_at_least_twice(*x))
-        .collect::<Vec<_>>();
-    println!(
-        "Sum of invalid IDs (with at least 2 repeated subsequences): {:?}",
-        invalid_ids_at_least_2.iter().sum::<u64>()
-    );
+pub struct Day2;
+
+/// Checks a range of IDs found at the given column of the input. IDs are split into their digits,
+/// so they can't be 0.
+fn check_ids(column: usize, s: &str, range: (u64, u64)) -> Result<(u64, u64), ParseError> {
+    if range.0 == 0 {
+        return Err(ParseError::new(
+            1,
+            column,
+            ParseErrorKind::OutOfRange {
+                found: s.to_string(),
+                expected: "a range of IDs starting from 1",
+            },
+        ));
+    }
+    Ok(range)
+}
+
+impl Solution for Day2 {
+    type Input = Vec<(u64, u64)>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    const TITLE: &'static str = "Gift Shop";
+    const PART_1_LABEL: &'static str = "Sum of invalid IDs (with exactly 2 repeated subsequences)";
+    const PART_2_LABEL: &'static str = "Sum of invalid IDs (with at least 2 repeated subsequences)";
+
+    #[instrument(skip(input))]
+    fn parse(input: &Input) -> Result<Self::Input, Error> {
+        // The input will always be made of a single line representing a comma separated list of ranges
+        let ranges = input.csv_ranges()?;
+        if let Some(line) = input.lines().next() {
+            for ((column, s), range) in line.csv_fields().zip(&ranges) {
+                check_ids(column, s, *range)?;
+            }
+        }
+        Ok(ranges)
+    }
+
+    fn validate(input: &Input) -> Vec<ParseError> {
+        let mut lines = input.lines();
+        let Some(line) = lines.next() else {
+            return vec![ParseError::new(1, 1, ParseErrorKind::EmptyInput)];
+        };
+        line.csv_fields()
+            .filter_map(|(column, s)| {
+                parse::ordered_range(s, column)
+                    .and_then(|x| check_ids(column, s, x))
+                    .err()
+            })
+            .chain(lines.filter(|x| !x.is_blank()).map(|x| {
+                ParseError::new(
+                    x.number,
+                    1,
+                    ParseErrorKind::UnexpectedToken {
+                        found: x.text.to_string(),
+                        expected: "the end of the input, as all the ranges are on the first line",
+                    },
+                )
+            }))
+            .collect()
+    }
+
+    fn part_1(ranges: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(ranges
+            .iter()
+            .flat_map(|x| generate_invalid_ids_exactly_twice(*x))
+            .sum())
+    }
+
+    fn part_2(ranges: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(ranges
+            .iter()
+            .flat_map(|x| generate_invalid_ids_at_least_twice(*x))
+            .sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    const EXAMPLE: &str = include_str!("day_2/example.txt");
+
+    #[test]
+    fn test_example() {
+        let ranges = Day2::parse(&Input::new(EXAMPLE)).unwrap();
+        assert_eq!(Day2::part_1(&ranges).unwrap(), 1227775554);
+        assert_eq!(Day2::part_2(&ranges).unwrap(), 4174379265);
+    }
+
+    #[test]
+    fn test_invalid_range() {
+        let error = Day2::parse(&Input::new("11-22,95-1x5")).unwrap_err();
+        assert!(matches!(error, Error::Parse(e) if e.line == 1 && e.column == 10));
+        let error = Day2::parse(&Input::new("")).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::Parse(ParseError {
+                kind: ParseErrorKind::EmptyInput,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_ranges_with_zero() {
+        // Counting the digits of 0 would panic, so such ranges are rejected when parsing
+        let error = Day2::parse(&Input::new("11-22,0-5")).unwrap_err();
+        assert!(matches!(error, Error::Parse(e) if e.column == 7));
+    }
+
+    #[test]
+    fn test_validate() {
+        let violations = Day2::validate(&Input::new("0-5,1x-3,30-20,4-9\n\n1-2\n"));
+        assert_eq!(
+            violations
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            vec![(1, 1), (1, 5), (1, 10), (3, 1)]
+        );
+        assert!(matches!(
+            violations[2].kind,
+            ParseErrorKind::ReversedRange { .. }
+        ));
+        assert!(Day2::validate(&Input::new(EXAMPLE)).is_empty());
+    }
 }
@@ -1,5 +1,5 @@
+use crate::trace::info;
 use std::cmp::{max, min};
-use tracing::{info, instrument};
 
 /// Generate all invalid IDs in a range.
 /// Invalid IDs are defined as a sequence of digits repeated twice.
@@ -12,18 +12,18 @@ use tracing::{info, instrument};
 /// 1. define the amount of digits of the start and end of the range, respectively as start_digits and end_digits;
 /// 2. define the range of valid amounts of digits of invalid IDs as (start_digits)..=(end_digits), filtering by even numbers only;
 /// 3. each value will represent the interval [10_u64.pow(i-1), 10_u64.pow(i)-1] - respectively base_first_half and ceil_first_half,
-///     which are constrained to the overall range passed as input;
+///    which are constrained to the overall range passed as input;
 /// 4. we're interested in the sub-interval made of the first half of each extreme of the previous interval,
-///     i.e., [base / 10_u64.pow(i / 2), ceil / 10_u64.pow(i / 2)]. For each value of this range, we can
-///     easily generate an invalid ID by repeating the value, i.e., invalid_id = interval_value * 10_u64.pow(i/2) + interval_value.
-#[instrument]
+///    i.e., [base / 10_u64.pow(i / 2), ceil / 10_u64.pow(i / 2)]. For each value of this range, we can
+///    easily generate an invalid ID by repeating the value, i.e., invalid_id = interval_value * 10_u64.pow(i/2) + interval_value.
+#[cfg_attr(any(debug_assertions, feature = "trace"), tracing::instrument)]
 pub fn generate_invalid_ids_exactly_twice(range: (u64, u64)) -> Vec<u64> {
     let (start, end) = range;
     let start_digits = start.ilog10() + 1;
     let end_digits = end.ilog10() + 1;
     (start_digits..=end_digits) // Example: 4,5,6,7
         .filter(|&i| i % 2 == 0) // 4,6
-        .map(|i| {
+        .flat_map(|i| {
             // Constraint the range to the overall range
             let base = max(start, 10_u64.pow(i - 1));
             let ceil = min(end, 10_u64.pow(i) - 1);
@@ -36,20 +36,19 @@ pub fn generate_invalid_ids_exactly_twice(range: (u64, u64)) -> Vec<u64> {
                 .filter(|&x| x >= start && x <= end) // todo this could probably be moved outside as an optimization
                 .collect::<Vec<_>>()
         })
-        .flatten()
         .collect::<Vec<_>>()
 }
 
 /// For the other half of the puzzle, the constraint is relaxed, leading to more invalid IDs to generate.
 /// Under the new rule that any sequence of digits repeated *at least* twice, for each amount of digits N
 /// that we need to analyze, invalid IDs can be constructed by repeating the first x digits, for x in the divisors of N.
-#[instrument]
+#[cfg_attr(any(debug_assertions, feature = "trace"), tracing::instrument)]
 pub fn generate_invalid_ids_at_least_twice(range: (u64, u64)) -> Vec<u64> {
     let (start, end) = range;
     let start_digits = start.ilog10() + 1;
     let end_digits = end.ilog10() + 1;
     let mut result = (start_digits..=end_digits)
-        .map(|digit_range| {
+        .flat_map(|digit_range| {
             // Constraint the range to the overall range
             let base = max(start, 10_u64.pow(digit_range - 1));
             let ceil = min(end, 10_u64.pow(digit_range) - 1);
@@ -60,7 +59,7 @@ pub fn generate_invalid_ids_at_least_twice(range: (u64, u64)) -> Vec<u64> {
 
             divisors
                 .iter()
-                .map(|&divisor| {
+                .flat_map(|&divisor| {
                     // If we have the list of divisors [1,2,3,5] (for i=30), we'll use 29, 28, 27, 25 to get the resulting number of first digits
                     let base_digits = base / 10_u64.pow(digit_range - divisor);
                     let ceil_digits = ceil / 10_u64.pow(digit_range - divisor);
@@ -83,10 +82,8 @@ pub fn generate_invalid_ids_at_least_twice(range: (u64, u64)) -> Vec<u64> {
                         .filter(|&el| el >= start && el <= end) // todo this could probably be moved outside as an optimization
                         .collect::<Vec<_>>()
                 })
-                .flatten()
                 .collect::<Vec<_>>()
         })
-        .flatten()
         .collect::<Vec<_>>();
     // Needed for sequences of digits such as 222222, which can be generated either with a divisor of 1 (2,2,2,2,2,2), 2 (22,22,22) or 3 (222,222)
     result.sort_unstable();
@@ -102,7 +99,10 @@ mod tests {
     #[test]
     fn test_generate_invalid_ids_simple() {
         assert_eq!(generate_invalid_ids_exactly_twice((2322, 2324)), vec![2323]);
-        assert_eq!(generate_invalid_ids_exactly_twice((1234, 1236)), vec![]);
+        assert_eq!(
+            generate_invalid_ids_exactly_twice((1234, 1236)),
+            Vec::<u64>::new()
+        );
         assert_eq!(
             generate_invalid_ids_exactly_twice((123, 1234)),
             vec![1010, 1111, 1212]
@@ -113,7 +113,7 @@ mod tests {
         );
         assert_eq!(
             generate_invalid_ids_exactly_twice((1000999, 1001001)),
-            vec![]
+            Vec::<u64>::new()
         );
         assert_eq!(
             generate_invalid_ids_exactly_twice((123, 1234567)),
@@ -240,7 +240,7 @@ mod tests {
         );
         assert_eq!(
             generate_invalid_ids_at_least_twice((1698522, 1698528)),
-            vec![]
+            Vec::<u64>::new()
         );
         assert_eq!(
             generate_invalid_ids_at_least_twice((446443, 446449)),
@@ -1,24 +1,90 @@
 mod bank;
 
 use crate::day_3::bank::Bank;
-use std::io::Lines;
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::{Error, Solution};
 use tracing::instrument;
 
-#[instrument(skip(input))]
-pub fn day_3_solution(input: Lines<impl std::io::BufRead>) {
-    let mut banks = input
-        .map_while(Result::ok)
-        .map(|x| Bank::from(x.as_str()))
-        .collect::<Vec<_>>();
-    println!(
-        "Total output joltage with 2 batteries: {}",
-        banks.iter().map(|x| x.joltage()).sum::<u64>()
-    );
-    banks.iter_mut().for_each(|x| {
-        x.set_max_enabled_batteries(12);
-    });
-    println!(
-        "Total output joltage with 12 batteries: {}",
-        banks.iter().map(|x| x.joltage()).sum::<u64>()
-    );
+pub struct Day3;
+
+const FIRST_HALF_ENABLED_BATTERIES: u32 = 2;
+const SECOND_HALF_ENABLED_BATTERIES: u32 = 12;
+
+impl Solution for Day3 {
+    type Input = Vec<Bank>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    const TITLE: &'static str = "Lobby";
+    const PART_1_LABEL: &'static str = "Total output joltage with 2 batteries";
+    const PART_2_LABEL: &'static str = "Total output joltage with 12 batteries";
+
+    #[instrument(skip(input))]
+    fn parse(input: &Input) -> Result<Self::Input, Error> {
+        input
+            .lines()
+            .map(|line| {
+                // Ensure every bank has enough batteries for the second half of the puzzle too
+                let mut bank =
+                    line.parse_with(|s| Bank::try_from((s, SECOND_HALF_ENABLED_BATTERIES)))?;
+                bank.set_max_enabled_batteries(FIRST_HALF_ENABLED_BATTERIES);
+                Ok(bank)
+            })
+            .collect()
+    }
+
+    fn validate(input: &Input) -> Vec<ParseError> {
+        input
+            .lines()
+            .flat_map(|line| {
+                Bank::validate(line.text, SECOND_HALF_ENABLED_BATTERIES)
+                    .into_iter()
+                    .map(move |e| e.on_line(line.number))
+            })
+            .collect()
+    }
+
+    fn part_1(banks: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(banks.iter().map(|x| x.joltage()).sum())
+    }
+
+    fn part_2(banks: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(banks
+            .iter()
+            .map(|x| {
+                let mut bank = x.clone();
+                bank.set_max_enabled_batteries(SECOND_HALF_ENABLED_BATTERIES);
+                bank.joltage()
+            })
+            .sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    const EXAMPLE: &str = include_str!("day_3/example.txt");
+
+    #[test]
+    fn test_example() {
+        let banks = Day3::parse(&Input::new(EXAMPLE)).unwrap();
+        assert_eq!(Day3::part_1(&banks).unwrap(), 357);
+        assert_eq!(Day3::part_2(&banks).unwrap(), 3121910778619);
+    }
+
+    #[test]
+    fn test_validate() {
+        let violations = Day3::validate(&Input::new("987654321111111\n12x4\n81111111111111y"));
+        assert_eq!(
+            violations
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            vec![(2, 3), (2, 1), (3, 15)]
+        );
+        assert!(Day3::validate(&Input::new(EXAMPLE)).is_empty());
+    }
 }
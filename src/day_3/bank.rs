@@ -1,5 +1,8 @@
-use tracing::{debug, info, instrument};
+use crate::error::{ParseError, ParseErrorKind};
+use crate::trace::{debug, info};
+use std::str::FromStr;
 
+#[derive(Clone)]
 pub struct Bank {
     batteries: Vec<u64>,
     max_enabled_batteries: u32,
@@ -14,33 +17,70 @@ impl Default for Bank {
     }
 }
 
-impl From<(&str, u32)> for Bank {
-    fn from(value: (&str, u32)) -> Self {
+/// Parses the joltage of the battery found at the given (0-based) index of a bank.
+fn parse_battery(i: usize, x: char) -> Result<u64, ParseError> {
+    x.to_digit(10).map(u64::from).ok_or(ParseError::new(
+        1,
+        i + 1,
+        ParseErrorKind::UnexpectedChar {
+            found: x,
+            expected: "a battery joltage digit",
+        },
+    ))
+}
+
+/// Ensures a bank has enough batteries to turn on the given amount of them.
+fn check_battery_count(found: usize, required: u32) -> Result<(), ParseError> {
+    if found < required as usize {
+        return Err(ParseError::new(
+            1,
+            1,
+            ParseErrorKind::NotEnoughBatteries {
+                found,
+                required: required as usize,
+            },
+        ));
+    }
+    Ok(())
+}
+
+impl TryFrom<(&str, u32)> for Bank {
+    type Error = ParseError;
+
+    fn try_from(value: (&str, u32)) -> Result<Self, Self::Error> {
         let bank = Bank {
             batteries: value
                 .0
                 .chars()
-                .map(|x| x.to_digit(10).unwrap() as u64)
-                .collect(),
+                .enumerate()
+                .map(|(i, x)| parse_battery(i, x))
+                .collect::<Result<_, _>>()?,
             max_enabled_batteries: value.1,
         };
-        if bank.batteries.len() < value.1 as usize {
-            panic!(
-                "Unexpected bank of less than {} batteries, can't proceed with a solution",
-                value.1
-            );
-        }
-        bank
+        check_battery_count(bank.batteries.len(), value.1)?;
+        Ok(bank)
     }
 }
 
-impl From<&str> for Bank {
-    fn from(value: &str) -> Self {
-        Bank::from((value, 2))
+impl FromStr for Bank {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Bank::try_from((s, Bank::default().max_enabled_batteries))
     }
 }
 
 impl Bank {
+    /// Lists every problem that would prevent a bank from being built out of the line, instead of
+    /// stopping at the first one.
+    pub fn validate(s: &str, max_enabled_batteries: u32) -> Vec<ParseError> {
+        s.chars()
+            .enumerate()
+            .filter_map(|(i, x)| parse_battery(i, x).err())
+            .chain(check_battery_count(s.chars().count(), max_enabled_batteries).err())
+            .collect()
+    }
+
     pub fn set_max_enabled_batteries(&mut self, max_enabled_batteries: u32) {
         self.max_enabled_batteries = max_enabled_batteries;
     }
@@ -64,7 +104,10 @@ impl Bank {
         battery + from
     }
 
-    #[instrument(skip(self))]
+    #[cfg_attr(
+        any(debug_assertions, feature = "trace"),
+        tracing::instrument(skip(self))
+    )]
     pub fn joltage(&self) -> u64 {
         let mut last_battery_id = None;
         let batteries = (0..self.max_enabled_batteries)
@@ -96,37 +139,63 @@ mod tests {
 
     #[test]
     fn test_parse_bank() {
-        let bank = Bank::from("123456789");
+        let bank = "123456789".parse::<Bank>().unwrap();
         assert_eq!(bank.batteries, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
     }
 
+    #[test]
+    fn test_parse_invalid_bank() {
+        assert_eq!(
+            "12a4".parse::<Bank>().err(),
+            Some(ParseError::new(
+                1,
+                3,
+                ParseErrorKind::UnexpectedChar {
+                    found: 'a',
+                    expected: "a battery joltage digit"
+                }
+            ))
+        );
+        assert_eq!(
+            Bank::try_from(("1234", 12)).err(),
+            Some(ParseError::new(
+                1,
+                1,
+                ParseErrorKind::NotEnoughBatteries {
+                    found: 4,
+                    required: 12
+                }
+            ))
+        );
+    }
+
     #[test]
     fn test_simple_joltage() {
-        let bank = Bank::from("123456789");
+        let bank = "123456789".parse::<Bank>().unwrap();
         assert_eq!(bank.joltage(), 89);
     }
 
     #[test]
     fn test_example() {
-        let bank = Bank::from("987654321111111");
+        let bank = "987654321111111".parse::<Bank>().unwrap();
         assert_eq!(bank.joltage(), 98);
-        let bank = Bank::from("811111111111119");
+        let bank = "811111111111119".parse::<Bank>().unwrap();
         assert_eq!(bank.joltage(), 89);
-        let bank = Bank::from("234234234234278");
+        let bank = "234234234234278".parse::<Bank>().unwrap();
         assert_eq!(bank.joltage(), 78);
-        let bank = Bank::from("818181911112111");
+        let bank = "818181911112111".parse::<Bank>().unwrap();
         assert_eq!(bank.joltage(), 92);
     }
 
     #[test]
     fn test_with_12_batteries() {
-        let bank = Bank::from(("987654321111111", 12));
+        let bank = Bank::try_from(("987654321111111", 12)).unwrap();
         assert_eq!(bank.joltage(), 987654321111);
-        let bank = Bank::from(("811111111111119", 12));
+        let bank = Bank::try_from(("811111111111119", 12)).unwrap();
         assert_eq!(bank.joltage(), 811111111119);
-        let bank = Bank::from(("234234234234278", 12));
+        let bank = Bank::try_from(("234234234234278", 12)).unwrap();
         assert_eq!(bank.joltage(), 434234234278);
-        let bank = Bank::from(("818181911112111", 12));
+        let bank = Bank::try_from(("818181911112111", 12)).unwrap();
         assert_eq!(bank.joltage(), 888911112111);
     }
 }
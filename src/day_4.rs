@@ -1,32 +1,76 @@
 use crate::day_4::grid::Grid;
-use std::io::Lines;
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::{Error, Solution};
 
 mod grid;
 
-fn solve_first_half(lines: &Vec<&str>) {
-    let mut grid = Grid::from_lines(lines);
-    println!(
-        "Number of reachable paper: {}",
-        grid.reachable_paper_count()
-    );
-}
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const TITLE: &'static str = "Printing Department";
+    const PART_1_LABEL: &'static str = "Number of reachable paper";
+    const PART_2_LABEL: &'static str = "Total number of rolls of paper that can be removed";
+
+    fn parse(input: &Input) -> Result<Self::Input, Error> {
+        Ok(Grid::from_input(input)?)
+    }
+
+    fn validate(input: &Input) -> Vec<ParseError> {
+        Grid::validate(input)
+    }
 
-fn solve_second_half(lines: &Vec<&str>) {
-    let mut grid = Grid::from_lines(lines);
-    let mut removed_paper_rolls = 0;
+    fn part_1(grid: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(grid.clone().reachable_paper_count())
+    }
 
-    while let Some(count) = grid.remove_reachable_paper() {
-        removed_paper_rolls += count;
+    fn part_2(grid: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(grid.simulate_removal().total())
     }
-    println!(
-        "Total number of rolls of paper that can be removed: {}",
-        removed_paper_rolls
-    )
 }
 
-pub fn day_4_solution(input: Lines<impl std::io::BufRead>) {
-    let lines = input.map_while(Result::ok).collect::<Vec<_>>();
-    let lines = lines.iter().map(|x| x.as_str()).collect::<Vec<_>>();
-    solve_first_half(&lines);
-    solve_second_half(&lines);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseErrorKind;
+    use test_log::test;
+
+    const EXAMPLE: &str = include_str!("day_4/example.txt");
+
+    #[test]
+    fn test_example() {
+        let grid = Day4::parse(&Input::new(EXAMPLE)).unwrap();
+        assert_eq!(Day4::part_1(&grid).unwrap(), 13);
+        assert_eq!(Day4::part_2(&grid).unwrap(), 43);
+    }
+
+    #[test]
+    fn test_blank_input() {
+        let error = Day4::parse(&Input::new("\n")).err();
+        assert!(matches!(
+            error,
+            Some(Error::Parse(e)) if e.kind == ParseErrorKind::EmptyInput && e.line == 1
+        ));
+        assert_eq!(
+            Day4::validate(&Input::new("\n")),
+            vec![ParseError::new(1, 1, ParseErrorKind::EmptyInput)]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let violations = Day4::validate(&Input::new("..@\n.#@\n@?\n"));
+        assert_eq!(
+            violations
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            vec![(2, 2), (3, 2), (3, 3)]
+        );
+        assert!(Day4::validate(&Input::new(EXAMPLE)).is_empty());
+    }
 }
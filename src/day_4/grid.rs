@@ -1,6 +1,8 @@
-use std::cmp::min;
+use crate::error::{ParseError, ParseErrorKind};
+use crate::grid::{self, Position};
+use crate::input::Input;
+use crate::trace::debug;
 use std::fmt::Display;
-use tracing::debug;
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 enum Cell {
@@ -9,104 +11,103 @@ enum Cell {
     Empty,
 }
 
-impl From<char> for Cell {
-    fn from(c: char) -> Self {
+impl Cell {
+    /// Whether there's a roll of paper in the cell, whether it has been marked as reachable or not.
+    fn is_paper(&self) -> bool {
+        matches!(self, Cell::Paper | Cell::ReachablePaper)
+    }
+}
+
+impl TryFrom<char> for Cell {
+    type Error = ParseErrorKind;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '.' => Cell::Empty,
-            '@' => Cell::Paper,
-            'x' => Cell::ReachablePaper,
-            _ => panic!("Unexpected cell value: {}", c),
+            '.' => Ok(Cell::Empty),
+            '@' => Ok(Cell::Paper),
+            'x' => Ok(Cell::ReachablePaper),
+            _ => Err(ParseErrorKind::UnexpectedChar {
+                found: c,
+                expected: "a grid cell ('.', '@' or 'x')",
+            }),
+        }
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cell::Paper => write!(f, "@"),
+            Cell::ReachablePaper => write!(f, "x"),
+            Cell::Empty => write!(f, "."),
         }
     }
 }
 
+/// Outcome of removing rolls of paper until none of them can be reached anymore.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Removal {
+    /// Number of rolls of paper removed in each round, in order.
+    pub per_round: Vec<usize>,
+}
+
+impl Removal {
+    pub fn total(&self) -> usize {
+        self.per_round.iter().sum()
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.per_round.len()
+    }
+}
+
+#[derive(Clone)]
 pub struct Grid {
-    /// 1D representation of the grid, with each row represented consecutively, i.e. [[1,2,3],[4,5,6]] becomes [1,2,3,4,5,6]
-    space: Vec<Cell>,
-    columns: usize,
-    rows: usize,
+    space: grid::Grid<Cell>,
 }
 
 impl Display for Grid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.space.chunks(self.columns) {
-            writeln!(
-                f,
-                "{}",
-                row.iter()
-                    .map(|x| match x {
-                        Cell::Paper => '@',
-                        Cell::ReachablePaper => 'x',
-                        Cell::Empty => '.',
-                    })
-                    .collect::<String>()
-            )?;
-        }
-        Ok(())
+        write!(f, "{}", self.space)
     }
 }
 
 impl Grid {
-    /// Allows generating a grid from a vec of lines, where each line is a row with a char representing a cell.
-    pub fn from_lines(lines: &Vec<&str>) -> Self {
-        if lines.is_empty() {
-            panic!("Empty grid")
-        }
-        let columns = lines.first().unwrap().len();
-        let rows = lines.len();
-        let mut vec = Vec::with_capacity(lines.len() * columns);
-        lines
-            .iter()
-            .for_each(|x| vec.extend(x.chars().map(Cell::from)));
-        Self {
-            space: vec,
-            columns,
-            rows,
-        }
+    /// Allows generating a grid from the input, where each line is a row with a char representing a cell.
+    pub fn from_input(input: &Input) -> Result<Self, ParseError> {
+        Ok(Self {
+            space: input.char_grid(Cell::try_from)?,
+        })
     }
 
-    fn has_paper(&self, x: usize, y: usize) -> Option<bool> {
-        self.space
-            .get(x + y * self.columns)
-            .map(|x| *x == Cell::Paper)
+    /// Lists every cell that isn't paper or empty space, and every row that isn't as wide as the
+    /// first one.
+    pub fn validate(input: &Input) -> Vec<ParseError> {
+        input.char_grid_violations(Cell::try_from)
     }
 
     /// Verifies whether a cell can be reached by a forklift.
     /// A cell is considered reachable if there are fewer than four rolls of paper in the eight adjagent positions.
-    fn is_reachable(&self, x: usize, y: usize) -> bool {
-        let mut paper_found = 0;
-        let previous_row = x.saturating_sub(1);
-        let next_row = min(x + 1, self.rows - 1);
-        let previous_column = y.saturating_sub(1);
-        let next_column = min(y + 1, self.columns - 1);
-
-        for row in previous_row..=next_row {
-            for column in previous_column..=next_column {
-                if row == x && column == y {
-                    continue;
-                }
-                let cell = self.has_paper(row, column);
-                if cell.unwrap_or(false) {
-                    paper_found += 1;
-                }
-            }
-        }
-        paper_found < 4
+    fn is_reachable(&self, position: Position) -> bool {
+        self.paper_neighbors(position) < 4
+    }
+
+    fn paper_neighbors(&self, position: Position) -> usize {
+        self.space
+            .adjacent_neighbors(position)
+            .filter(|x| self.space[*x].is_paper())
+            .count()
     }
 
     pub fn mark_reachable_paper(&mut self) {
         let cells_to_mark = self
             .space
             .iter()
-            .enumerate()
-            .filter(|(_, x)| **x == Cell::Paper)
-            .filter_map(|(i, _)| {
-                self.is_reachable(i % self.columns, i / self.rows)
-                    .then_some(i)
-            })
+            .filter(|(position, x)| **x == Cell::Paper && self.is_reachable(*position))
+            .map(|(position, _)| position)
             .collect::<Vec<_>>();
-        for i in cells_to_mark {
-            self.space[i] = Cell::ReachablePaper;
+        for position in cells_to_mark {
+            self.space[position] = Cell::ReachablePaper;
         }
     }
 
@@ -116,25 +117,46 @@ impl Grid {
 
         self.space
             .iter()
-            .filter(|x| **x == Cell::ReachablePaper)
+            .filter(|(_, x)| **x == Cell::ReachablePaper)
             .count()
     }
 
-    pub fn remove_reachable_paper(&mut self) -> Option<usize> {
-        let count = self.reachable_paper_count();
-        self.space = self
+    /// Simulates removing all reachable paper, round after round, until none is left reachable.
+    /// Rather than rescanning the grid every round, the amount of paper around each cell is kept
+    /// up to date as rolls are removed, and a roll is queued for the next round as soon as that
+    /// amount drops below four, so that every cell is visited a bounded number of times.
+    pub fn simulate_removal(&self) -> Removal {
+        let mut neighbors = grid::Grid::new(
+            self.space.rows(),
+            self.space.columns(),
+            self.space
+                .positions()
+                .map(|x| self.paper_neighbors(x))
+                .collect(),
+        );
+        let mut round = self
             .space
             .iter()
-            .map(|x| {
-                if *x == Cell::ReachablePaper {
-                    Cell::Empty
-                } else {
-                    *x
+            .filter(|(position, x)| x.is_paper() && neighbors[*position] < 4)
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        let mut per_round = Vec::new();
+        while !round.is_empty() {
+            per_round.push(round.len());
+            let mut next_round = Vec::new();
+            for position in round {
+                for neighbor in self.space.adjacent_neighbors(position) {
+                    neighbors[neighbor] -= 1;
+                    // Cells already below the threshold have been queued in a previous round
+                    if neighbors[neighbor] == 3 && self.space[neighbor].is_paper() {
+                        next_round.push(neighbor);
+                    }
                 }
-            })
-            .collect();
-        debug!(%count, "space state after removal\n{self}");
-        if count == 0 { None } else { Some(count) }
+            }
+            round = next_round;
+        }
+        debug!(?per_round, "paper removal rounds");
+        Removal { per_round }
     }
 }
 
@@ -143,9 +165,13 @@ mod tests {
     use super::*;
     use test_log::test;
 
+    fn parse_grid(lines: &[&str]) -> Result<Grid, ParseError> {
+        Grid::from_input(&Input::new(lines.join("\n")))
+    }
+
     #[test]
     fn test_example() {
-        let mut grid = Grid::from_lines(&vec![
+        let mut grid = parse_grid(&[
             "..@@.@@@@.",
             "@@@.@.@.@@",
             "@@@@@.@.@@",
@@ -156,7 +182,103 @@ mod tests {
             "@.@@@.@@@@",
             ".@@@@@@@@.",
             "@.@.@@@.@.",
-        ]);
+        ])
+        .unwrap();
         assert_eq!(grid.reachable_paper_count(), 13);
     }
+
+    #[test]
+    fn test_example_removal() {
+        let grid = parse_grid(&[
+            "..@@.@@@@.",
+            "@@@.@.@.@@",
+            "@@@@@.@.@@",
+            "@.@@@@..@.",
+            "@@.@@@@.@@",
+            ".@@@@@@@.@",
+            ".@.@.@.@@@",
+            "@.@@@.@@@@",
+            ".@@@@@@@@.",
+            "@.@.@@@.@.",
+        ])
+        .unwrap();
+        let removal = grid.simulate_removal();
+        assert_eq!(removal.per_round, vec![13, 12, 7, 5, 2, 1, 1, 1, 1]);
+        assert_eq!(removal.rounds(), 9);
+        assert_eq!(removal.total(), 43);
+    }
+
+    #[test]
+    fn test_removal_without_paper() {
+        let grid = parse_grid(&["...", "..."]).unwrap();
+        assert_eq!(grid.simulate_removal(), Removal { per_round: vec![] });
+    }
+
+    #[test]
+    fn test_removal_of_a_full_grid() {
+        // Every roll of paper is eventually reachable, the grid being peeled from its corners
+        let removal = parse_grid(&["@@@@", "@@@@", "@@@@"])
+            .unwrap()
+            .simulate_removal();
+        assert_eq!(removal.per_round, vec![4, 2, 4, 2]);
+        assert_eq!(removal.total(), 12);
+    }
+
+    #[test]
+    fn test_removal_of_marked_paper() {
+        // Paper marked as reachable is still paper, both to remove and to count as a neighbor
+        let removal = |lines: &[&str]| parse_grid(lines).unwrap().simulate_removal();
+        assert_eq!(
+            removal(&["x@@x", "@@@@", "x@@x"]),
+            removal(&["@@@@", "@@@@", "@@@@"])
+        );
+        assert_eq!(
+            removal(&["@@@", "@x@", "@@@"]),
+            removal(&["@@@", "@@@", "@@@"])
+        );
+        let mut grid = parse_grid(&["@@@@", "@@@@", "@@@@"]).unwrap();
+        grid.mark_reachable_paper();
+        assert_eq!(grid.simulate_removal().per_round, vec![4, 2, 4, 2]);
+    }
+
+    #[test]
+    fn test_non_square_grid() {
+        // Only the corners have fewer than four neighbors
+        let mut grid = parse_grid(&["@@@@", "@@@@"]).unwrap();
+        assert_eq!(grid.reachable_paper_count(), 4);
+        let mut grid = parse_grid(&["@@@@@@", "@@@@@@", "@@@@@@"]).unwrap();
+        assert_eq!(grid.reachable_paper_count(), 4);
+        let mut grid = parse_grid(&["@", "@", "@", "@", "@"]).unwrap();
+        assert_eq!(grid.reachable_paper_count(), 5);
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        assert_eq!(
+            parse_grid(&["..@", ".#@"]).err(),
+            Some(ParseError::new(
+                2,
+                2,
+                ParseErrorKind::UnexpectedChar {
+                    found: '#',
+                    expected: "a grid cell ('.', '@' or 'x')"
+                }
+            ))
+        );
+        assert_eq!(
+            parse_grid(&["..@", ".@"]).err(),
+            Some(ParseError::new(
+                2,
+                3,
+                ParseErrorKind::RaggedRow {
+                    expected: 3,
+                    found: 2
+                }
+            ))
+        );
+        assert_eq!(
+            parse_grid(&[]).err(),
+            Some(ParseError::new(1, 1, ParseErrorKind::EmptyInput))
+        );
+    }
 }
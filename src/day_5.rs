@@ -1,31 +1,95 @@
 mod ranges;
 
 use crate::day_5::ranges::Ranges;
-use std::io::Lines;
-
-pub fn day_5_solution(input: Lines<impl std::io::BufRead>) {
-    let mut ranges = vec![];
-    let mut ingredient_ids = vec![];
-    let mut parsing_ranges = true;
-    input.map_while(Result::ok).for_each(|line| {
-        if line.is_empty() {
-            parsing_ranges = false;
-            return;
-        }
-        if parsing_ranges {
-            let (start, end) = line.split_once('-').unwrap();
-            let start = start.parse::<usize>().unwrap();
-            let end = end.parse::<usize>().unwrap();
-            ranges.push((start, end));
-        } else {
-            ingredient_ids.push(line.parse::<usize>().unwrap());
-        }
-    });
-    let ranges = Ranges::new(ranges);
-    let fresh_ingredients = ingredient_ids
-        .iter()
-        .filter(|x| ranges.contains(**x))
-        .count();
-    println!("Fresh ingredients available: {}", fresh_ingredients);
-    println!("Total amount of fresh ingredient IDs: {}", ranges.count());
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::{Error, Solution};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    /// The fresh ingredient ID ranges, followed by the available ingredient IDs
+    type Input = (Ranges, Vec<usize>);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const TITLE: &'static str = "Cafeteria";
+    const PART_1_LABEL: &'static str = "Fresh ingredients available";
+    const PART_2_LABEL: &'static str = "Total amount of fresh ingredient IDs";
+
+    fn parse(input: &Input) -> Result<Self::Input, Error> {
+        let mut sections = input.sections().into_iter();
+        let ranges = sections
+            .next()
+            .unwrap_or_default()
+            .iter()
+            .map(|line| line.range())
+            .collect::<Result<_, _>>()?;
+        let ingredient_ids = sections
+            .flatten()
+            .map(|line| line.number())
+            .collect::<Result<_, _>>()?;
+        Ok((Ranges::new(ranges), ingredient_ids))
+    }
+
+    fn validate(input: &Input) -> Vec<ParseError> {
+        let mut sections = input.sections().into_iter();
+        let ranges = sections.next().unwrap_or_default();
+        ranges
+            .iter()
+            .filter_map(|line| line.range::<usize>().err())
+            .chain(
+                sections
+                    .flatten()
+                    .filter_map(|line| line.number::<usize>().err()),
+            )
+            .collect()
+    }
+
+    fn part_1((ranges, ingredient_ids): &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(ingredient_ids
+            .iter()
+            .filter(|x| ranges.contains(**x))
+            .count())
+    }
+
+    fn part_2((ranges, _): &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(ranges.count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    const EXAMPLE: &str = include_str!("day_5/example.txt");
+
+    #[test]
+    fn test_example() {
+        let inventory = Day5::parse(&Input::new(EXAMPLE)).unwrap();
+        assert_eq!(Day5::part_1(&inventory).unwrap(), 3);
+        assert_eq!(Day5::part_2(&inventory).unwrap(), 14);
+    }
+
+    #[test]
+    fn test_invalid_inventory() {
+        let error = Day5::parse(&Input::new("3-5\n10\n\n1")).unwrap_err();
+        assert!(matches!(error, Error::Parse(e) if e.line == 2 && e.column == 3));
+        let error = Day5::parse(&Input::new("3-5\n\n1\n5-")).unwrap_err();
+        assert!(matches!(error, Error::Parse(e) if e.line == 4 && e.column == 1));
+    }
+
+    #[test]
+    fn test_validate() {
+        let violations = Day5::validate(&Input::new("3-5\n10\n20-12\n\n1\nx\n5-"));
+        assert_eq!(
+            violations
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            vec![(2, 3), (3, 1), (6, 1), (7, 1)]
+        );
+        assert!(Day5::validate(&Input::new(EXAMPLE)).is_empty());
+    }
 }
@@ -1,7 +1,8 @@
+use crate::trace::debug;
 use std::cmp::max;
-use tracing::{debug, instrument};
 
 /// Describes a defragmented union of ranges.
+#[derive(Debug)]
 pub struct Ranges {
     ranges: Vec<(usize, usize)>,
 }
@@ -28,10 +29,13 @@ impl Ranges {
     /// 1. we take a pair of ranges to analyze. This step can result in either both ranges being kept, or only the merge being kept.
     /// 2. we compare the start of the second range with the end of the first. If the former is greater, the intersection between the two ranges is empty and they both must be kept.
     /// 3. otherwise, the second range overlaps with the first, and we must merge them.
-    ///     a. the starting point of the merged range must be the one of the first, as it's the minimum of the two by construction.
-    ///     b. the ending point will be the maximum between the two ranges.
+    ///    a. the starting point of the merged range must be the one of the first, as it's the minimum of the two by construction.
+    ///    b. the ending point will be the maximum between the two ranges.
     /// 4. we proceed analyzing the next pair, for which the first range will either be the newly merged range, or the original second range we were trying to merge.
-    #[instrument(skip(self))]
+    #[cfg_attr(
+        any(debug_assertions, feature = "trace"),
+        tracing::instrument(skip(self))
+    )]
     fn defrag(&mut self) {
         self.ranges = self.ranges.iter().fold(
             Vec::with_capacity(self.ranges.len()),
@@ -73,25 +77,25 @@ mod tests {
 
     #[test]
     fn test_defrag_single_long_range() {
-        let mut ranges = Ranges::new(vec![(1, 3), (4, 5), (6, 7), (8, 10), (11, 12)]);
+        let ranges = Ranges::new(vec![(1, 3), (4, 5), (6, 7), (8, 10), (11, 12)]);
         assert_eq!(ranges.ranges, vec![(1, 12)]);
     }
 
     #[test]
     fn test_defrag_fully_contained() {
-        let mut ranges = Ranges::new(vec![(1, 12), (2, 4)]);
+        let ranges = Ranges::new(vec![(1, 12), (2, 4)]);
         assert_eq!(ranges.ranges, vec![(1, 12)]);
     }
 
     #[test]
     fn test_defrag_partially_contained() {
-        let mut ranges = Ranges::new(vec![(1, 5), (3, 7)]);
+        let ranges = Ranges::new(vec![(1, 5), (3, 7)]);
         assert_eq!(ranges.ranges, vec![(1, 7)]);
     }
 
     #[test]
     fn test_defrag_interruptions() {
-        let mut ranges = Ranges::new(vec![(1, 3), (6, 8), (7, 12)]);
+        let ranges = Ranges::new(vec![(1, 3), (6, 8), (7, 12)]);
         assert_eq!(ranges.ranges, vec![(1, 3), (6, 12)]);
     }
 
@@ -105,7 +109,7 @@ mod tests {
 
     #[test]
     fn test_unordered_ranges() {
-        let mut ranges = Ranges::new(vec![(7, 12), (1, 3), (6, 8)]);
+        let ranges = Ranges::new(vec![(7, 12), (1, 3), (6, 8)]);
         assert_eq!(ranges.ranges, vec![(1, 3), (6, 12)]);
     }
 
@@ -1,18 +1,70 @@
 mod problem;
 
 use crate::day_6::problem::Problem;
-use std::io::Lines;
-
-pub fn day_6_solution(input: Lines<impl std::io::BufRead>) {
-    let lines = input.map_while(Result::ok).collect::<Vec<_>>();
-    let lines = lines.iter().map(|x| x.as_str()).collect::<Vec<_>>();
-    let problems = Problem::from_table_l2r(&lines);
-    let sum = problems.iter().map(|x| x.solve()).sum::<i64>();
-    println!("Grand total of all the problems (normal math): {}", sum);
-    let problems_r2l = Problem::from_table_r2l(&lines);
-    let sum_r2l = problems_r2l.iter().map(|x| x.solve()).sum::<i64>();
-    println!(
-        "Grand total of all the problems (right-to-left): {}",
-        sum_r2l
-    );
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::{Error, Solution};
+
+pub struct Day6;
+
+/// The problems of the table, read in the way of each half of the puzzle.
+pub struct Worksheet {
+    left_to_right: Vec<Problem>,
+    right_to_left: Vec<Problem>,
+}
+
+impl Solution for Day6 {
+    type Input = Worksheet;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    const TITLE: &'static str = "Trash Compactor";
+    const PART_1_LABEL: &'static str = "Grand total of all the problems (normal math)";
+    const PART_2_LABEL: &'static str = "Grand total of all the problems (right-to-left)";
+
+    fn parse(input: &Input) -> Result<Self::Input, Error> {
+        Ok(Worksheet {
+            left_to_right: Problem::from_table_l2r(input)?,
+            right_to_left: Problem::from_table_r2l(input)?,
+        })
+    }
+
+    fn validate(input: &Input) -> Vec<ParseError> {
+        Problem::validate(input)
+    }
+
+    fn part_1(worksheet: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(worksheet.left_to_right.iter().map(|x| x.solve()).sum())
+    }
+
+    fn part_2(worksheet: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(worksheet.right_to_left.iter().map(|x| x.solve()).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    const EXAMPLE: &str = include_str!("day_6/example.txt");
+
+    #[test]
+    fn test_example() {
+        let worksheet = Day6::parse(&Input::new(EXAMPLE)).unwrap();
+        assert_eq!(Day6::part_1(&worksheet).unwrap(), 4277556);
+        assert_eq!(Day6::part_2(&worksheet).unwrap(), 3263827);
+    }
+
+    #[test]
+    fn test_invalid_table() {
+        let error = Day6::parse(&Input::new("1 2\n3 x\n* +")).err();
+        assert!(matches!(error, Some(Error::Parse(e)) if e.line == 2 && e.column == 3));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Day6::validate(&Input::new(EXAMPLE)).is_empty());
+        assert_eq!(Day6::validate(&Input::new("1 2\n3 x\n* -")).len(), 2);
+    }
 }
@@ -1,5 +1,8 @@
+use crate::error::{ParseError, ParseErrorKind};
+use crate::input::Input;
+use crate::trace::debug;
+use std::cmp::min;
 use std::str::FromStr;
-use tracing::debug;
 
 /// Describes both operators and operands (i32s)
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -10,16 +13,19 @@ enum Op {
 }
 
 impl FromStr for Op {
-    type Err = ();
+    type Err = ParseErrorKind;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "*" => Ok(Op::Mul),
             "+" => Ok(Op::Add),
-            _ => Ok(s
+            _ => s
                 .parse::<i64>()
                 .map(Op::Expr)
-                .expect("Unexpected string found while parsing operator/operand")),
+                .map_err(|_| ParseErrorKind::UnexpectedToken {
+                    found: s.to_string(),
+                    expected: "a number or an operator ('+' or '*')",
+                }),
         }
     }
 }
@@ -34,29 +40,58 @@ impl Problem {
     /// It is assumed that each column has an equal amount of numbers, with the last element being an
     /// operation, and that each column is separated by an arbitrary amount of spaces.
     /// The result will be a mathematical expression with a series of operands followed by a single operator to apply to them.
-    pub fn from_table_l2r(table: &Vec<&str>) -> Vec<Problem> {
-        let x = table
-            .iter()
-            .map(|x| {
-                x.split_whitespace()
-                    .map(|y| y.parse::<Op>().unwrap())
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>();
-        // Transpose the vec of vecs to get columns instead of rows - shamelessly taken from https://stackoverflow.com/questions/64498617/how-to-transpose-a-vector-of-vectors-in-rust
-        // It is safe to assume each row will have the same amount of columns, as the amount of columns is equal to the amount of problems we have
-        let columns = x[0].len();
-        let mut iters: Vec<_> = x.into_iter().map(|x| x.into_iter()).collect();
-        let problems: Vec<_> = (0..columns)
-            .map(|_| Problem {
-                ops: iters
-                    .iter_mut()
-                    .map(|n| n.next().unwrap())
-                    .collect::<Vec<_>>(),
+    pub fn from_table_l2r(input: &Input) -> Result<Vec<Problem>, ParseError> {
+        let table = input.lines().collect::<Vec<_>>();
+        let rows = match table.split_last() {
+            // Operators may only appear in the last row, and operands in all the other ones
+            Some((operator_row, operand_rows)) => operand_rows
+                .iter()
+                .map(|line| line.integers().map(|x| x.map(Op::Expr)).collect())
+                .chain(std::iter::once(
+                    operator_row
+                        .tokens()
+                        .map(|(column, token)| {
+                            token
+                                .parse::<Op>()
+                                .ok()
+                                .filter(|op| !matches!(op, Op::Expr(_)))
+                                .ok_or_else(|| {
+                                    ParseError::new(
+                                        operator_row.number,
+                                        column,
+                                        ParseErrorKind::UnexpectedToken {
+                                            found: token.to_string(),
+                                            expected: "an operator ('+' or '*')",
+                                        },
+                                    )
+                                })
+                        })
+                        .collect(),
+                ))
+                .collect::<Result<Vec<Vec<_>>, _>>()?,
+            None => vec![],
+        };
+        // Each row must have the same amount of columns, as the amount of columns is equal to the amount of problems we have
+        let columns = rows
+            .first()
+            .ok_or(ParseError::new(1, 1, ParseErrorKind::EmptyInput))?
+            .len();
+        if let Some((row, ops)) = rows.iter().enumerate().find(|(_, x)| x.len() != columns) {
+            return Err(ParseError::new(
+                row + 1,
+                1,
+                ParseErrorKind::RaggedRow {
+                    expected: columns,
+                    found: ops.len(),
+                },
+            ));
+        }
+        // Transpose the rows to get a problem out of each column
+        Ok((0..columns)
+            .map(|column| Problem {
+                ops: rows.iter().map(|ops| ops[column]).collect(),
             })
-            .collect();
-
-        problems
+            .collect())
     }
 
     /// Builds a vec of problems from a matrix, reading from right to left and considering number positions in each column.
@@ -64,73 +99,210 @@ impl Problem {
     /// SAFETY:
     /// It is assumed that each column may only contain spaces at its start or at the end, but not between digits.
     /// If a space is present between digits, any digit above the space *will* be ignored, as it is unclear how such case should be handled from the instructions.
-    pub fn from_table_r2l(table: &Vec<&str>) -> Vec<Problem> {
-        let columns = table[0].len();
-        let mut iter_of_chars = table.iter().map(|x| x.chars().rev()).collect::<Vec<_>>();
+    pub fn from_table_r2l(input: &Input) -> Result<Vec<Problem>, ParseError> {
+        let rows = input
+            .lines()
+            .map(|x| x.text.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let columns = rows
+            .first()
+            .ok_or(ParseError::new(1, 1, ParseErrorKind::EmptyInput))?
+            .len();
+        if let Some((row, chars)) = rows.iter().enumerate().find(|(_, x)| x.len() != columns) {
+            return Err(ParseError::new(
+                row + 1,
+                min(chars.len(), columns) + 1,
+                ParseErrorKind::RaggedRow {
+                    expected: columns,
+                    found: chars.len(),
+                },
+            ));
+        }
+        // The bottom row holds either an operator or a space, with the operand digits being above it
+        let (operator_row, operand_rows) = rows.split_last().expect("The table is not empty");
 
-        // Transpose the vec of vecs to get columns instead of rows - shamelessly taken from https://stackoverflow.com/questions/64498617/how-to-transpose-a-vector-of-vectors-in-rust
-        // It is safe to assume each row will have the same amount of columns, as the amount of columns is equal to the amount of problems we have
         let mut skip_delimiter_column = false;
         let mut problems = vec![];
-        let orphan_operands: Vec<_> = (0..columns).fold(vec![], |mut acc, col_idx| {
+        let mut operands = vec![];
+        for col_idx in (0..columns).rev() {
             debug!(%col_idx, "Processing column");
-            let mut column = iter_of_chars
-                .iter_mut()
-                .map(|n| {
-                    n.next().expect(
-                        "Couldn't get next column - ensure all rows have the same amount of chars",
-                    )
-                })
-                // At this point, we'll have an iterator of chars of the column being processed, and we need to convert it back to an operand
-                // to_string can be optimized - we just need it because we're dealing with chars
-                .rev(); // Start from the bottom cell, either an operator or a space, followed by the operand digits
             if skip_delimiter_column {
-                // Process the column by just skipping it
-                column.for_each(|_| {});
                 skip_delimiter_column = false;
-                return acc;
+                continue;
             }
-            // Store the operand temporarily, to use it later on to decide whether to fold the accumulator or continue to the next column
-            let operator = column
-                .next()
-                .expect("Unexpected end of column while parsing operator/operand");
-            let operand = column
-                .filter(|x| x != &' ')
-                .map(|x| {
-                    x.to_digit(10)
-                        .expect("Unexpected non-digit character found while parsing operand")
-                        as i64
-                })
+            let operand = operand_rows
+                .iter()
                 .enumerate()
-                .fold(0, |acc, (i, digit)| acc + (digit * 10_i64.pow(i as u32)));
+                .map(|(row, chars)| (row, chars[col_idx]))
+                .filter(|(_, x)| x != &' ')
+                .try_fold(0, |acc, (row, x)| {
+                    x.to_digit(10)
+                        .map(|digit| acc * 10 + digit as i64)
+                        .ok_or(ParseError::new(
+                            row + 1,
+                            col_idx + 1,
+                            ParseErrorKind::UnexpectedChar {
+                                found: x,
+                                expected: "an operand digit",
+                            },
+                        ))
+                })?;
+            let operator = operator_row[col_idx];
             debug!(?operator, ?operand, "Pushing operand");
-            acc.push(Op::Expr(operand));
+            operands.push(Op::Expr(operand));
             if operator != ' ' {
-                let op = operator
-                    .to_string()
-                    .parse::<Op>()
-                    .expect("Failed to parse operator");
-                debug!(?op, ?acc, "Found operator, flushing problem");
-                // Flush the accumulator
-                acc.push(op);
-                problems.push(Problem { ops: acc.clone() });
-                acc.clear();
+                let op = match operator {
+                    '*' => Op::Mul,
+                    '+' => Op::Add,
+                    _ => {
+                        return Err(ParseError::new(
+                            rows.len(),
+                            col_idx + 1,
+                            ParseErrorKind::UnexpectedChar {
+                                found: operator,
+                                expected: "an operator ('+' or '*')",
+                            },
+                        ));
+                    }
+                };
+                debug!(?op, ?operands, "Found operator, flushing problem");
+                // Flush the operands
+                operands.push(op);
+                problems.push(Problem {
+                    ops: std::mem::take(&mut operands),
+                });
                 // Signal to the next iteration that we need to skip the delimiter column
                 skip_delimiter_column = true;
             }
-            acc
-        });
-        if !orphan_operands.is_empty() {
-            panic!(
-                "The table is not properly formatted. There are orphan operands that couldn't be folded into a valid problem."
-            );
+        }
+        if !operands.is_empty() {
+            return Err(ParseError::new(
+                rows.len(),
+                1,
+                ParseErrorKind::OrphanOperands,
+            ));
         }
 
-        problems
+        Ok(problems)
+    }
+
+    /// Lists every problem of the table that would prevent either half of the puzzle from reading
+    /// it, instead of stopping at the first one:
+    /// - every operand must be a plain number and every operator a '+' or a '*', so that any other
+    ///   character is reported once, as part of its token;
+    /// - every row must have as many tokens as the first one, and be as wide as it;
+    /// - the digits of an operand read from top to bottom can't be split by spaces;
+    /// - the column before each operator must be blank, as it's skipped when reading from right to left;
+    /// - the leftmost problem must have an operator, or its operands would be left without one.
+    pub fn validate(input: &Input) -> Vec<ParseError> {
+        let table = input.lines().collect::<Vec<_>>();
+        let Some(operator_row) = table.last() else {
+            return vec![ParseError::new(1, 1, ParseErrorKind::EmptyInput)];
+        };
+        let mut violations = vec![];
+        let first_tokens = table[0].tokens().count();
+        let first_width = table[0].text.chars().count();
+        for (row, line) in table.iter().enumerate() {
+            let (is_valid, expected): (fn(&str) -> bool, _) = if row == table.len() - 1 {
+                (|x| x == "+" || x == "*", "an operator ('+' or '*')")
+            } else {
+                (|x| x.chars().all(|x| x.is_ascii_digit()), "a number")
+            };
+            violations.extend(line.tokens().filter(|(_, token)| !is_valid(token)).map(
+                |(column, token)| {
+                    ParseError::new(
+                        row + 1,
+                        column,
+                        ParseErrorKind::UnexpectedToken {
+                            found: token.to_string(),
+                            expected,
+                        },
+                    )
+                },
+            ));
+            let (tokens, width) = (line.tokens().count(), line.text.chars().count());
+            if tokens != first_tokens {
+                violations.push(ParseError::new(
+                    row + 1,
+                    1,
+                    ParseErrorKind::RaggedRow {
+                        expected: first_tokens,
+                        found: tokens,
+                    },
+                ));
+            } else if width != first_width {
+                violations.push(ParseError::new(
+                    row + 1,
+                    min(width, first_width) + 1,
+                    ParseErrorKind::RaggedRow {
+                        expected: first_width,
+                        found: width,
+                    },
+                ));
+            }
+        }
+
+        let rows = table
+            .iter()
+            .map(|x| x.text.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let columns = rows.iter().map(Vec::len).min().unwrap_or_default();
+        let (operators, operands) = rows.split_last().expect("The table is not empty");
+        for col in 0..columns {
+            let digits = operands
+                .iter()
+                .enumerate()
+                .filter(|(_, x)| x[col].is_ascii_digit())
+                .map(|(row, _)| row)
+                .collect::<Vec<_>>();
+            if let (Some(&first), Some(&last)) = (digits.first(), digits.last()) {
+                violations.extend((first..last).filter(|row| operands[*row][col] == ' ').map(
+                    |row| {
+                        ParseError::new(
+                            row + 1,
+                            col + 1,
+                            ParseErrorKind::UnexpectedChar {
+                                found: ' ',
+                                expected: "an operand digit, as digits can't be split by spaces",
+                            },
+                        )
+                    },
+                ));
+            }
+            if col > 0 && operators[col] != ' ' {
+                violations.extend(
+                    operands
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, x)| x[col - 1] != ' ')
+                        .map(|(row, x)| {
+                            ParseError::new(
+                                row + 1,
+                                col,
+                                ParseErrorKind::UnexpectedChar {
+                                    found: x[col - 1],
+                                    expected: "a space separating two problems",
+                                },
+                            )
+                        }),
+                );
+            }
+        }
+        // The column before the leftmost operator is skipped, so only the ones before it are orphans
+        let first_operator = operator_row.text.chars().position(|x| x != ' ');
+        if columns > 0 && first_operator.is_none_or(|x| x >= 2) {
+            violations.push(ParseError::new(
+                table.len(),
+                1,
+                ParseErrorKind::OrphanOperands,
+            ));
+        }
+        violations
     }
 
     /// Solves the problem by evaluating the operator at the end of the problem to all the operands.
     /// Panics if the accumulator does not hold a single value at the end, which means the problem was incorrectly formulated.
+    /// This can't happen for problems built from a table, as their parsers reject tables without a row of operators.
     pub fn solve(&self) -> i64 {
         let accumulator =
             self.ops
@@ -161,9 +333,13 @@ mod tests {
     use super::*;
     use test_log::test;
 
+    fn table(lines: &[&str]) -> Input {
+        Input::new(lines.join("\n"))
+    }
+
     #[test]
     fn test_simple_op_parsing() {
-        let ops = vec!["1", "2", "+", "*"]
+        let ops = ["1", "2", "+", "*"]
             .iter()
             .map(|el| el.parse::<Op>().expect("Failed to parse valid Op"))
             .collect::<Vec<_>>();
@@ -172,8 +348,8 @@ mod tests {
 
     #[test]
     fn test_problems_table_parsing() {
-        let table = vec!["1 2 3 4", "5 6 7 8", "* + + *"];
-        let problems = Problem::from_table_l2r(&table);
+        let lines = ["1 2 3 4", "5 6 7 8", "* + + *"];
+        let problems = Problem::from_table_l2r(&table(&lines)).unwrap();
         assert_eq!(
             problems,
             vec![
@@ -195,8 +371,8 @@ mod tests {
 
     #[test]
     fn test_problems_solving() {
-        let table = vec!["1 2 3 4", "5 6 7 8", "* + + *"];
-        let problems = Problem::from_table_l2r(&table);
+        let lines = ["1 2 3 4", "5 6 7 8", "* + + *"];
+        let problems = Problem::from_table_l2r(&table(&lines)).unwrap();
         assert_eq!(
             problems.iter().map(|p| p.solve()).collect::<Vec<_>>(),
             vec![5, 8, 10, 32]
@@ -205,13 +381,13 @@ mod tests {
 
     #[test]
     fn test_problems_table_parsing_r2l_example() {
-        let table = vec![
+        let lines = [
             "123 328  51 64 ",
             " 45 64  387 23 ",
             "  6 98  215 314",
             "*   +   *   +  ",
         ];
-        let problems = Problem::from_table_r2l(&table);
+        let problems = Problem::from_table_r2l(&table(&lines)).unwrap();
         assert_eq!(
             problems,
             vec![
@@ -230,4 +406,112 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_validate() {
+        let lines = [
+            "123 328  51 64 ",
+            " 45 6x  387 23 ",
+            "  6 98 1 15 314",
+            "  5  8  21 3",
+            "*   +   *   +  ",
+        ];
+        let violations = Problem::validate(&table(&lines));
+        assert_eq!(
+            violations
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            vec![(2, 5), (3, 1), (4, 13), (3, 9), (3, 8)]
+        );
+        let violations = Problem::validate(&table(&["123 4", "567 8", "  + *"]));
+        assert_eq!(
+            violations
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            vec![(1, 2), (2, 2), (3, 1)]
+        );
+        assert_eq!(violations[2].kind, ParseErrorKind::OrphanOperands);
+        assert!(
+            Problem::validate(&table(&[
+                "123 328  51 64 ",
+                " 45 64  387 23 ",
+                "  6 98  215 314",
+                "*   +   *   +  ",
+            ]))
+            .is_empty()
+        );
+        assert_eq!(
+            Problem::validate(&table(&[])),
+            vec![ParseError::new(1, 1, ParseErrorKind::EmptyInput)]
+        );
+    }
+
+    #[test]
+    fn test_invalid_table_l2r() {
+        assert_eq!(
+            Problem::from_table_l2r(&table(&["1 2", "3 x", "* +"])).err(),
+            Some(ParseError::new(
+                2,
+                3,
+                ParseErrorKind::UnexpectedToken {
+                    found: "x".to_string(),
+                    expected: "a number"
+                }
+            ))
+        );
+        assert_eq!(
+            Problem::from_table_l2r(&table(&["1 2", "3 4"])).err(),
+            Some(ParseError::new(
+                2,
+                1,
+                ParseErrorKind::UnexpectedToken {
+                    found: "3".to_string(),
+                    expected: "an operator ('+' or '*')"
+                }
+            ))
+        );
+        assert_eq!(
+            Problem::from_table_l2r(&table(&["1 2", "3", "* +"])).err(),
+            Some(ParseError::new(
+                2,
+                1,
+                ParseErrorKind::RaggedRow {
+                    expected: 2,
+                    found: 1
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_invalid_table_r2l() {
+        assert_eq!(
+            Problem::from_table_r2l(&table(&["12 3", "4 5", "*  +"])).err(),
+            Some(ParseError::new(
+                2,
+                4,
+                ParseErrorKind::RaggedRow {
+                    expected: 4,
+                    found: 3
+                }
+            ))
+        );
+        assert_eq!(
+            Problem::from_table_r2l(&table(&["12 3", "4a 5", "*  +"])).err(),
+            Some(ParseError::new(
+                2,
+                2,
+                ParseErrorKind::UnexpectedChar {
+                    found: 'a',
+                    expected: "an operand digit"
+                }
+            ))
+        );
+        assert_eq!(
+            Problem::from_table_r2l(&table(&["12 3", "45 6", "   +"])).err(),
+            Some(ParseError::new(3, 1, ParseErrorKind::OrphanOperands))
+        );
+    }
 }
@@ -1,15 +1,89 @@
 mod manifold;
 
 use crate::day_7::manifold::Manifold;
-use std::io::Lines;
-
-pub fn day_7_solution(input: Lines<impl std::io::BufRead>) {
-    let lines = input.map_while(Result::ok).collect::<Vec<_>>();
-    let lines = lines.iter().map(|x| x.as_str()).collect::<Vec<_>>();
-    let manifold = Manifold::from_lines(lines);
-    println!("{}", manifold);
-    println!(
-        "Number of times the beam splits: {}",
-        manifold.count_splits()
-    );
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::trace::debug;
+use crate::{Error, Solution};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Manifold;
+    type Part1 = usize;
+    type Part2 = u128;
+
+    const TITLE: &'static str = "Laboratories";
+    const PART_1_LABEL: &'static str = "Number of times the beam splits";
+    const PART_2_LABEL: &'static str = "Number of timelines of a single tachyon particle";
+
+    fn parse(input: &Input) -> Result<Self::Input, Error> {
+        let manifold = Manifold::from_input(input)?;
+        debug!("manifold\n{manifold}");
+        Ok(manifold)
+    }
+
+    fn validate(input: &Input) -> Vec<ParseError> {
+        Manifold::validate(input)
+    }
+
+    fn part_1(manifold: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(manifold.count_splits())
+    }
+
+    fn part_2(manifold: &Self::Input) -> Result<Self::Part2, Error> {
+        manifold.count_timelines()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseErrorKind;
+    use test_log::test;
+
+    const EXAMPLE: &str = include_str!("day_7/example.txt");
+
+    #[test]
+    fn test_example() {
+        let manifold = Day7::parse(&Input::new(EXAMPLE)).unwrap();
+        assert_eq!(Day7::part_1(&manifold).unwrap(), 21);
+        assert_eq!(Day7::part_2(&manifold).unwrap(), 40);
+    }
+
+    #[test]
+    fn test_validate() {
+        let violations = Day7::validate(&Input::new("..S..\n.S.x\n..S.."));
+        assert_eq!(
+            violations
+                .iter()
+                .map(|x| (x.line, x.column, &x.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    2,
+                    4,
+                    &ParseErrorKind::UnexpectedChar {
+                        found: 'x',
+                        expected: "a manifold component ('S', '^' or '.')"
+                    }
+                ),
+                (
+                    2,
+                    5,
+                    &ParseErrorKind::RaggedRow {
+                        expected: 5,
+                        found: 4
+                    }
+                ),
+                (2, 2, &ParseErrorKind::ExtraBeam),
+                (3, 3, &ParseErrorKind::ExtraBeam),
+            ]
+        );
+        assert_eq!(
+            Day7::validate(&Input::new("...\n...")),
+            vec![ParseError::new(1, 1, ParseErrorKind::MissingBeam)]
+        );
+        assert!(Day7::validate(&Input::new(EXAMPLE)).is_empty());
+    }
 }
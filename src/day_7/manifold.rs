@@ -1,11 +1,9 @@
-use std::cmp::min;
+use crate::Error;
+use crate::error::{ParseError, ParseErrorKind};
+use crate::grid::{Grid, Position};
+use crate::input::Input;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Eq, PartialEq)]
-struct Position {
-    x: usize,
-    y: usize,
-}
 #[derive(Debug, Eq, PartialEq)]
 enum ManifoldComponentType {
     Beam,
@@ -14,13 +12,16 @@ enum ManifoldComponentType {
 }
 
 impl TryFrom<char> for ManifoldComponentType {
-    type Error = ();
+    type Error = ParseErrorKind;
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             'S' => Ok(Self::Beam),
             '^' => Ok(Self::Splitter),
             '.' => Ok(Self::EmptySpace),
-            _ => Err(()),
+            _ => Err(ParseErrorKind::UnexpectedChar {
+                found: value,
+                expected: "a manifold component ('S', '^' or '.')",
+            }),
         }
     }
 }
@@ -35,71 +36,65 @@ impl Display for ManifoldComponentType {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct ManifoldComponent {
-    component_type: ManifoldComponentType,
-    position: Position,
-}
-
-impl Display for ManifoldComponent {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.component_type)
-    }
-}
-
 #[derive(Debug)]
 pub struct Manifold {
-    components: Vec<ManifoldComponent>,
-    columns: usize,
-    rows: usize,
+    components: Grid<ManifoldComponentType>,
+    beam: Position,
 }
 
-/// All methods of Manifold expect the components vec to be sorted by columns and rows.
 impl Manifold {
-    /// Builds a new instance of a Manifold.
-    /// The inner components representation is guaranteed to be ordered by columns and rows.
-    pub fn from_lines(lines: Vec<&str>) -> Self {
-        Self {
-            columns: lines.first().expect("There's no input to parse from").len(),
-            rows: lines.len(),
-            components: lines
-                .into_iter()
+    /// Builds a new instance of a Manifold, which is guaranteed to contain a beam.
+    pub fn from_input(input: &Input) -> Result<Self, ParseError> {
+        let components = input.char_grid(ManifoldComponentType::try_from)?;
+        let beam = components
+            .find(|el| *el == ManifoldComponentType::Beam)
+            .ok_or(ParseError::new(1, 1, ParseErrorKind::MissingBeam))?;
+        Ok(Self { components, beam })
+    }
+
+    /// Lists every cell that isn't a manifold component, every row that isn't as wide as the first
+    /// one, and every beam entry point but the first one, or the lack of any.
+    pub fn validate(input: &Input) -> Vec<ParseError> {
+        let mut violations = input.char_grid_violations(ManifoldComponentType::try_from);
+        let mut beams = input.lines().flat_map(|line| {
+            line.text
+                .chars()
                 .enumerate()
-                .flat_map(|(row, line)| {
-                    line.chars()
-                        .enumerate()
-                        .filter_map(|(column, char)| {
-                            let component_type = ManifoldComponentType::try_from(char).ok()?;
-                            Some(ManifoldComponent {
-                                component_type,
-                                position: Position { x: column, y: row },
-                            })
-                        })
-                        .collect::<Vec<_>>()
+                .filter(|(_, x)| *x == 'S')
+                .map(move |(col, _)| {
+                    ParseError::new(line.number, col + 1, ParseErrorKind::ExtraBeam)
                 })
-                .collect(),
+        });
+        if beams.next().is_none() && !input.as_str().is_empty() {
+            violations.push(ParseError::new(1, 1, ParseErrorKind::MissingBeam));
         }
+        violations.extend(beams);
+        violations
+    }
+
+    /// Columns reached by the beams coming out of a splitter in the given column. A beam that
+    /// would go past a side of the manifold leaves it.
+    fn split(&self, col: usize) -> impl Iterator<Item = usize> + use<> {
+        let columns = self.components.columns();
+        [col.checked_sub(1), Some(col + 1)]
+            .into_iter()
+            .flatten()
+            .filter(move |x| *x < columns)
     }
 
     /// Counts the number of times the beam splits in the manifold.
     pub fn count_splits(&self) -> usize {
-        let beam = self.get_beam();
+        let columns = self.components.columns();
         let mut splits = 0;
-        let mut valid_columns = Vec::with_capacity(self.columns);
-        valid_columns.push(beam.position.x);
-        for row in 0..self.rows {
+        let mut valid_columns = Vec::with_capacity(columns);
+        valid_columns.push(self.beam.col);
+        for row in 0..self.components.rows() {
             valid_columns = valid_columns
                 .into_iter()
                 .map(|col| {
-                    let cell = self
-                        .components
-                        .get((row * self.columns) + col)
-                        .expect("The input is not sorted by columns and rows");
-                    if cell.component_type == ManifoldComponentType::Splitter {
-                        let previous = cell.position.x.saturating_sub(1);
-                        let next = min(cell.position.x + 1, self.columns - 1);
+                    if self.components[Position::new(row, col)] == ManifoldComponentType::Splitter {
                         splits += 1;
-                        vec![previous, next]
+                        self.split(col).collect()
                     } else {
                         vec![col]
                     }
@@ -116,27 +111,161 @@ impl Manifold {
         splits
     }
 
-    fn get_beam(&self) -> &ManifoldComponent {
-        self.components
-            .iter()
-            .find(|el| el.component_type == ManifoldComponentType::Beam)
-            .expect("The input contains no beam")
+    /// Counts the number of distinct timelines a single tachyon particle can end up in.
+    /// Each splitter doubles the timelines reaching it, so instead of deduplicating columns we keep
+    /// track of how many paths reach each column in the current row. Paths leaving the manifold
+    /// from its sides are completed timelines, and are accounted for separately.
+    /// Fails if there are more timelines than fit in a `u128`.
+    pub fn count_timelines(&self) -> Result<u128, Error> {
+        let overflow = || Error::Overflow {
+            what: "the number of timelines",
+        };
+        let add = |x: &mut u128, paths: u128| -> Result<(), Error> {
+            *x = x.checked_add(paths).ok_or_else(overflow)?;
+            Ok(())
+        };
+        let columns = self.components.columns();
+        let mut timelines = vec![0_u128; columns];
+        let mut exited = 0_u128;
+        timelines[self.beam.col] = 1;
+        for row in 0..self.components.rows() {
+            let mut next_row = vec![0_u128; columns];
+            for (col, &paths) in timelines
+                .iter()
+                .enumerate()
+                .filter(|(_, paths)| **paths > 0)
+            {
+                if self.components[Position::new(row, col)] == ManifoldComponentType::Splitter {
+                    let branches = self.split(col).collect::<Vec<_>>();
+                    for x in &branches {
+                        add(&mut next_row[*x], paths)?;
+                    }
+                    for _ in branches.len()..2 {
+                        add(&mut exited, paths)?;
+                    }
+                } else {
+                    add(&mut next_row[col], paths)?;
+                }
+            }
+            timelines = next_row;
+        }
+        timelines
+            .into_iter()
+            .try_fold(exited, |total, x| total.checked_add(x))
+            .ok_or_else(overflow)
     }
 }
 
 impl Display for Manifold {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut iter = self.components.iter();
-        for _ in 0..self.rows {
-            for _ in 0..self.columns {
-                write!(
-                    f,
-                    "{}",
-                    iter.next().expect("Not enough components to display")
-                )?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        write!(f, "{}", self.components)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    fn parse_manifold(lines: &[&str]) -> Result<Manifold, ParseError> {
+        Manifold::from_input(&Input::new(lines.join("\n")))
+    }
+
+    const EXAMPLE: [&str; 16] = [
+        ".......S.......",
+        "...............",
+        ".......^.......",
+        "...............",
+        "......^.^......",
+        "...............",
+        ".....^.^.^.....",
+        "...............",
+        "....^.^...^....",
+        "...............",
+        "...^.^...^.^...",
+        "...............",
+        "..^...^.....^..",
+        "...............",
+        ".^.^.^.^.^...^.",
+        "...............",
+    ];
+
+    #[test]
+    fn test_example_splits() {
+        let manifold = parse_manifold(&EXAMPLE).unwrap();
+        assert_eq!(manifold.count_splits(), 21);
+    }
+
+    #[test]
+    fn test_example_timelines() {
+        let manifold = parse_manifold(&EXAMPLE).unwrap();
+        assert_eq!(manifold.count_timelines().unwrap(), 40);
+    }
+
+    #[test]
+    fn test_timelines_without_splitters() {
+        let manifold = parse_manifold(&["..S..", ".....", "....."]).unwrap();
+        assert_eq!(manifold.count_timelines().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_timelines_leaving_the_manifold() {
+        // The left branch of the first splitter leaves the manifold, but it's still a valid timeline
+        let manifold = parse_manifold(&["S..", "^..", "...", ".^.", "..."]).unwrap();
+        assert_eq!(manifold.count_timelines().unwrap(), 3);
+        // Both counts agree that the beam leaves the manifold rather than staying on its side
+        assert_eq!(manifold.count_splits(), 2);
+        let manifold = parse_manifold(&["S.", "^.", "..", "^."]).unwrap();
+        assert_eq!(manifold.count_splits(), 1);
+        assert_eq!(manifold.count_timelines().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_timelines_overflow() {
+        // Every row splits every path, away from the sides, so that the timelines double each time
+        let rows = 130;
+        let columns = 2 * rows + 3;
+        let mut lines = vec![format!("{}S{}", ".".repeat(rows + 1), ".".repeat(rows + 1))];
+        lines.extend((0..rows).map(|row| {
+            (0..columns)
+                .map(|col| if (col + row) % 2 == 1 { '^' } else { '.' })
+                .collect::<String>()
+        }));
+        let manifold =
+            parse_manifold(&lines.iter().map(|x| x.as_str()).collect::<Vec<_>>()).unwrap();
+        assert!(matches!(
+            manifold.count_timelines(),
+            Err(Error::Overflow { .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_manifold() {
+        assert_eq!(
+            parse_manifold(&["..S..", "..x.."]).err(),
+            Some(ParseError::new(
+                2,
+                3,
+                ParseErrorKind::UnexpectedChar {
+                    found: 'x',
+                    expected: "a manifold component ('S', '^' or '.')"
+                }
+            ))
+        );
+        assert_eq!(
+            parse_manifold(&["..S..", "...."]).err(),
+            Some(ParseError::new(
+                2,
+                5,
+                ParseErrorKind::RaggedRow {
+                    expected: 5,
+                    found: 4
+                }
+            ))
+        );
+        assert_eq!(
+            parse_manifold(&[".....", "....."]).err(),
+            Some(ParseError::new(1, 1, ParseErrorKind::MissingBeam))
+        );
     }
 }
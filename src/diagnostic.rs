@@ -0,0 +1,158 @@
+//! Reports of parsing errors in the style of compiler diagnostics, showing the offending line of
+//! the input with the location of the error underlined.
+
+use crate::error::{ParseError, ParseErrorKind};
+use std::fmt::{Display, Formatter};
+
+/// A parsing error, along with the input it was found in and the name to refer to the input by,
+/// e.g. its path. Renders as:
+/// ```text
+/// error: unexpected character 'x', expected a manifold component ('S', '^' or '.')
+///  --> inputs/day_7.txt:2:3
+///   |
+/// 2 | ..x..
+///   |   ^
+///   = hint: check that the input belongs to the puzzle of this day
+/// ```
+pub struct Diagnostic<'a> {
+    pub name: &'a str,
+    pub source: &'a str,
+    pub error: &'a ParseError,
+}
+
+impl<'a> Diagnostic<'a> {
+    pub fn new(name: &'a str, source: &'a str, error: &'a ParseError) -> Self {
+        Self {
+            name,
+            source,
+            error,
+        }
+    }
+}
+
+/// Suggests what may have gone wrong with the input for each kind of error.
+fn hint(kind: &ParseErrorKind) -> &'static str {
+    match kind {
+        ParseErrorKind::EmptyInput => {
+            "make sure the input was downloaded completely, and that the right file was given"
+        }
+        ParseErrorKind::UnexpectedChar { .. }
+        | ParseErrorKind::UnexpectedToken { .. }
+        | ParseErrorKind::OutOfRange { .. }
+        | ParseErrorKind::ReversedRange { .. } => {
+            "check that the input belongs to the puzzle of this day"
+        }
+        ParseErrorKind::UnexpectedEndOfLine { .. } => "the line may have been cut short",
+        ParseErrorKind::RaggedRow { .. } => {
+            "every row must be as wide as the first one, check that an editor didn't trim \
+            trailing whitespace"
+        }
+        ParseErrorKind::NotEnoughBatteries { .. } => {
+            "every bank needs at least as many batteries as the ones turned on in both halves"
+        }
+        ParseErrorKind::OrphanOperands => {
+            "the last row of the table must have an operator ('+' or '*') under each problem"
+        }
+        ParseErrorKind::MissingBeam | ParseErrorKind::ExtraBeam => {
+            "a manifold has a single beam entry point, marked with an 'S'"
+        }
+    }
+}
+
+impl Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ParseError { line, column, kind } = self.error;
+        let gutter = " ".repeat(line.to_string().len());
+        writeln!(f, "error: {kind}")?;
+        writeln!(f, "{gutter}--> {}:{line}:{column}", self.name)?;
+        if let Some(text) = self.source.lines().nth(line.saturating_sub(1)) {
+            // Tabs are kept in the padding, so that the caret lines up with the text above it
+            let padding = text
+                .chars()
+                .chain(std::iter::repeat(' '))
+                .take(column.saturating_sub(1))
+                .map(|x| if x == '\t' { '\t' } else { ' ' })
+                .collect::<String>();
+            let width = match kind {
+                ParseErrorKind::UnexpectedToken { found, .. }
+                | ParseErrorKind::OutOfRange { found, .. }
+                | ParseErrorKind::ReversedRange { found } => found.chars().count().max(1),
+                _ => 1,
+            };
+            writeln!(f, "{gutter} |")?;
+            writeln!(f, "{line} | {text}")?;
+            writeln!(f, "{gutter} | {padding}{}", "^".repeat(width))?;
+        }
+        writeln!(f, "{gutter} = hint: {}", hint(kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use crate::registry::{self, YEAR};
+    use crate::runner::Part;
+    use test_log::test;
+
+    #[test]
+    fn test_render() {
+        let error = ParseError::new(
+            2,
+            7,
+            ParseErrorKind::UnexpectedToken {
+                found: "1x5".to_string(),
+                expected: "a number",
+            },
+        );
+        assert_eq!(
+            Diagnostic::new("day_2.txt", "\n11-22,95-1x5", &error).to_string(),
+            "error: unexpected \"1x5\", expected a number\n \
+            --> day_2.txt:2:7\n  \
+            |\n\
+            2 | 11-22,95-1x5\n  \
+            |       ^^^\n  \
+            = hint: check that the input belongs to the puzzle of this day\n"
+        );
+    }
+
+    #[test]
+    fn test_render_without_line() {
+        let error = ParseError::new(1, 1, ParseErrorKind::EmptyInput);
+        assert_eq!(
+            Diagnostic::new("<stdin>", "", &error).to_string(),
+            "error: the input is empty\n \
+            --> <stdin>:1:1\n  \
+            = hint: make sure the input was downloaded completely, and that the right file was given\n"
+        );
+    }
+
+    #[test]
+    fn test_render_with_tabs() {
+        let error = ParseError::new(1, 3, ParseErrorKind::MissingBeam);
+        let diagnostic = Diagnostic::new("input", "\t.x", &error).to_string();
+        assert!(diagnostic.contains("1 | \t.x\n  | \t ^\n"));
+    }
+
+    /// Every day reports where its input is wrong, so that the diagnostic underlines it.
+    #[test]
+    fn test_every_day_points_at_the_error() {
+        let cases = [
+            (1, "L68\nL30\nU48", "3 | U48\n  | ^\n"),
+            (2, "11-22,95-1x5", "1 | 11-22,95-1x5\n  |          ^^^\n"),
+            (3, "987654321111111\n8119", "2 | 8119\n  | ^\n"),
+            (4, "..@\n.#@", "2 | .#@\n  |  ^\n"),
+            (5, "3-5\n10\n\n1", "2 | 10\n  |   ^\n"),
+            (6, "1 2\n3 x\n* +", "2 | 3 x\n  |   ^\n"),
+            (7, "..S..\n....", "2 | ....\n  |     ^\n"),
+        ];
+        for (day, source, expected) in cases {
+            let entry = registry::find(YEAR, day).unwrap();
+            let Err(Error::Parse(error)) = entry.solve(source, &[Part::One, Part::Two]) else {
+                panic!("day {day} should fail to parse {source:?}");
+            };
+            let diagnostic = Diagnostic::new("input", source, &error).to_string();
+            assert!(diagnostic.contains(expected), "day {day}:\n{diagnostic}");
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use crate::runner::Part;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// Errors that can occur while solving a puzzle.
+#[derive(Debug)]
+pub enum Error {
+    /// A file couldn't be read or written
+    Io {
+        action: IoAction,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// There's no puzzle input at the given path
+    InputNotFound(PathBuf),
+    /// The puzzle input doesn't have the expected format
+    Parse(ParseError),
+    /// There's no solution for the requested day
+    UnimplementedDay(u8),
+    /// The solution of the day doesn't solve the requested half of the puzzle yet
+    UnimplementedPart { day: u8, part: Part },
+    /// The file of known-good answers is malformed
+    Answers(toml::de::Error),
+    /// The configuration file is malformed
+    Config(toml::de::Error),
+    /// The file recording previously submitted answers is malformed
+    Submissions(toml::de::Error),
+    /// No session token is available to authenticate against the Advent of Code website
+    MissingSession,
+    /// The request to the Advent of Code website failed
+    Http(ureq::Error),
+    /// An answer is too large to be represented
+    Overflow { what: &'static str },
+    /// The day to scaffold already has a solution
+    DayAlreadyExists(u8),
+    /// A source file doesn't contain the place where a new day should be registered
+    MissingRegistration {
+        path: PathBuf,
+        expected: &'static str,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io {
+                action,
+                path,
+                source,
+            } => write!(f, "couldn't {action} {}: {source}", path.display()),
+            Error::InputNotFound(path) => {
+                write!(f, "no puzzle input found at {}", path.display())
+            }
+            Error::Parse(e) => write!(f, "invalid puzzle input at {e}"),
+            Error::UnimplementedDay(day) => write!(f, "day {day} has not been implemented yet"),
+            Error::UnimplementedPart { day, part } => {
+                write!(f, "part {part} of day {day} has not been implemented yet")
+            }
+            Error::Answers(e) => write!(f, "invalid answers file: {e}"),
+            Error::Config(e) => write!(f, "invalid configuration file: {e}"),
+            Error::Submissions(e) => write!(f, "invalid submissions file: {e}"),
+            Error::MissingSession => write!(
+                f,
+                "no session token found, set AOC_SESSION or `session` in the configuration file"
+            ),
+            Error::Http(e) => write!(f, "request to the Advent of Code website failed: {e}"),
+            Error::Overflow { what } => write!(f, "{what} is too large to be computed"),
+            Error::DayAlreadyExists(day) => write!(f, "day {day} already exists"),
+            Error::MissingRegistration { path, expected } => write!(
+                f,
+                "couldn't find where to register the new day in {}, expected {expected}",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse(e) => Some(e),
+            Error::InputNotFound(_)
+            | Error::UnimplementedDay(_)
+            | Error::UnimplementedPart { .. }
+            | Error::MissingSession
+            | Error::Overflow { .. }
+            | Error::DayAlreadyExists(_)
+            | Error::MissingRegistration { .. } => None,
+            Error::Answers(e) | Error::Config(e) | Error::Submissions(e) => Some(e),
+            Error::Http(e) => Some(e),
+        }
+    }
+}
+
+impl Error {
+    pub fn io(action: IoAction, path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Error::Io {
+            action,
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+/// What was being done with a file when it failed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum IoAction {
+    Read,
+    Write,
+    CreateDir,
+}
+
+impl Display for IoAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IoAction::Read => write!(f, "read"),
+            IoAction::Write => write!(f, "write"),
+            IoAction::CreateDir => write!(f, "create the directory"),
+        }
+    }
+}
+
+impl From<ureq::Error> for Error {
+    fn from(e: ureq::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+/// Describes what is wrong with a puzzle input, and where the problem was found.
+/// Both lines and columns are 1-based, as most editors display them. Parsers working on a single
+/// line report errors on line 1, leaving it to the caller to relocate them with [`ParseError::on_line`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+
+    /// Moves the error to the given line, keeping its column.
+    pub fn on_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    /// There's nothing to parse
+    EmptyInput,
+    /// A character that doesn't belong at this position
+    UnexpectedChar { found: char, expected: &'static str },
+    /// A whitespace or punctuation delimited token that doesn't belong at this position
+    UnexpectedToken {
+        found: String,
+        expected: &'static str,
+    },
+    /// The line ends before the parser found everything it needed
+    UnexpectedEndOfLine { expected: &'static str },
+    /// A row of a table or grid doesn't have the same width as the first one
+    RaggedRow { expected: usize, found: usize },
+    /// A bank doesn't have enough batteries to turn on
+    NotEnoughBatteries { found: usize, required: usize },
+    /// Operands at the end of a table that aren't followed by an operator
+    OrphanOperands,
+    /// The manifold has no tachyon beam entry point
+    MissingBeam,
+    /// The manifold has more than one tachyon beam entry point
+    ExtraBeam,
+    /// A number that is well-formed, but outside of the values the puzzle allows
+    OutOfRange {
+        found: String,
+        expected: &'static str,
+    },
+    /// A range whose end comes before its start
+    ReversedRange { found: String },
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyInput => write!(f, "the input is empty"),
+            Self::UnexpectedChar { found, expected } => {
+                write!(f, "unexpected character {found:?}, expected {expected}")
+            }
+            Self::UnexpectedToken { found, expected } => {
+                write!(f, "unexpected {found:?}, expected {expected}")
+            }
+            Self::UnexpectedEndOfLine { expected } => {
+                write!(f, "unexpected end of line, expected {expected}")
+            }
+            Self::RaggedRow { expected, found } => {
+                write!(f, "row has {found} columns, expected {expected}")
+            }
+            Self::NotEnoughBatteries { found, required } => write!(
+                f,
+                "bank has {found} batteries, but at least {required} are required"
+            ),
+            Self::OrphanOperands => write!(f, "operands are not followed by an operator"),
+            Self::MissingBeam => write!(f, "there's no beam entry point ('S') in the manifold"),
+            Self::ExtraBeam => write!(
+                f,
+                "there's more than one beam entry point ('S') in the manifold"
+            ),
+            Self::OutOfRange { found, expected } => {
+                write!(f, "{found} is out of range, expected {expected}")
+            }
+            Self::ReversedRange { found } => write!(f, "range {found} ends before it starts"),
+        }
+    }
+}
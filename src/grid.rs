@@ -0,0 +1,335 @@
+//! Two-dimensional grid shared by the puzzles whose input is a map of characters.
+
+use crate::error::{ParseError, ParseErrorKind};
+use std::cmp::min;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// Position of a cell in a grid, with both the row and column starting from 0 at the top left.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Position {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Moves the position by the given offsets, unless it'd end up on a negative row or column.
+    pub fn offset(&self, rows: isize, cols: isize) -> Option<Position> {
+        Some(Position {
+            row: self.row.checked_add_signed(rows)?,
+            col: self.col.checked_add_signed(cols)?,
+        })
+    }
+}
+
+/// Offsets of the neighbors sharing a side with a cell
+const ORTHOGONAL_OFFSETS: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+/// Offsets of the neighbors sharing either a side or a corner with a cell
+const ADJACENT_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid<T> {
+    /// 1D representation of the grid, with each row represented consecutively, i.e. [[1,2,3],[4,5,6]] becomes [1,2,3,4,5,6]
+    cells: Vec<T>,
+    rows: usize,
+    columns: usize,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid out of its cells, listed row by row.
+    ///
+    /// Panics if the amount of cells doesn't match the size of the grid.
+    pub fn new(rows: usize, columns: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            rows * columns,
+            "A {rows}x{columns} grid can't hold {} cells",
+            cells.len()
+        );
+        Self {
+            cells,
+            rows,
+            columns,
+        }
+    }
+
+    /// Allows generating a grid from a vec of lines, where each line is a row with a char representing a cell.
+    /// All the rows must have the same amount of cells, and there must be at least one of them.
+    pub fn from_lines(
+        lines: &[&str],
+        parse_cell: impl Fn(char) -> Result<T, ParseErrorKind>,
+    ) -> Result<Self, ParseError> {
+        let columns = lines
+            .first()
+            .ok_or(ParseError::new(1, 1, ParseErrorKind::EmptyInput))?
+            .chars()
+            .count();
+        if columns == 0 {
+            return Err(ParseError::new(1, 1, ParseErrorKind::EmptyInput));
+        }
+        let mut cells = Vec::with_capacity(lines.len() * columns);
+        for (row, line) in lines.iter().enumerate() {
+            let len = cells.len();
+            for (col, c) in line.chars().enumerate() {
+                cells.push(parse_cell(c).map_err(|e| ParseError::new(row + 1, col + 1, e))?);
+            }
+            let found = cells.len() - len;
+            if found != columns {
+                return Err(ParseError::new(
+                    row + 1,
+                    min(found, columns) + 1,
+                    ParseErrorKind::RaggedRow {
+                        expected: columns,
+                        found,
+                    },
+                ));
+            }
+        }
+        Ok(Self::new(lines.len(), columns, cells))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.row < self.rows && position.col < self.columns
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.row * self.columns + position.col])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.contains(position)
+            .then(|| &mut self.cells[position.row * self.columns + position.col])
+    }
+
+    /// Iterates over all positions of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + use<T> {
+        let columns = self.columns;
+        (0..self.rows).flat_map(move |row| (0..columns).map(move |col| Position { row, col }))
+    }
+
+    /// Iterates over all cells of the grid along with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Finds the position of the first cell, row by row, satisfying the predicate.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.columns..(row + 1) * self.columns]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.columns, "Column {col} is out of bounds");
+        self.cells.iter().skip(col).step_by(self.columns)
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.columns)
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.columns).map(|col| self.column(col))
+    }
+
+    fn neighbors<'a>(
+        &self,
+        position: Position,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + use<'a, T> {
+        let (rows, columns) = (self.rows, self.columns);
+        offsets
+            .iter()
+            .filter_map(move |&(row, col)| position.offset(row, col))
+            .filter(move |x| x.row < rows && x.col < columns)
+    }
+
+    /// Iterates over the positions of the (up to 4) cells sharing a side with the given one.
+    pub fn orthogonal_neighbors(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = Position> + use<T> {
+        self.neighbors(position, &ORTHOGONAL_OFFSETS)
+    }
+
+    /// Iterates over the positions of the (up to 8) cells sharing either a side or a corner with the given one.
+    pub fn adjacent_neighbors(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = Position> + use<T> {
+        self.neighbors(position, &ADJACENT_OFFSETS)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position)
+            .unwrap_or_else(|| panic!("Position {position:?} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("Position {position:?} is out of bounds"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_digit(c: char) -> Result<u32, ParseErrorKind> {
+        c.to_digit(10).ok_or(ParseErrorKind::UnexpectedChar {
+            found: c,
+            expected: "a digit",
+        })
+    }
+
+    /// A 2x4 grid
+    fn wide_grid() -> Grid<u32> {
+        Grid::from_lines(&["1234", "5678"], parse_digit).unwrap()
+    }
+
+    #[test]
+    fn test_from_lines() {
+        let grid = wide_grid();
+        assert_eq!((grid.rows(), grid.columns()), (2, 4));
+        assert_eq!(grid[Position::new(0, 3)], 4);
+        assert_eq!(grid[Position::new(1, 0)], 5);
+        assert_eq!(grid.get(Position::new(2, 0)), None);
+        assert_eq!(grid.get(Position::new(0, 4)), None);
+        assert_eq!(
+            Grid::from_lines(&["12", "3x"], parse_digit).err(),
+            Some(ParseError::new(
+                2,
+                2,
+                ParseErrorKind::UnexpectedChar {
+                    found: 'x',
+                    expected: "a digit"
+                }
+            ))
+        );
+        assert_eq!(
+            Grid::from_lines(&["12", "345"], parse_digit).err(),
+            Some(ParseError::new(
+                2,
+                3,
+                ParseErrorKind::RaggedRow {
+                    expected: 2,
+                    found: 3
+                }
+            ))
+        );
+        assert_eq!(
+            Grid::from_lines(&[], parse_digit).err(),
+            Some(ParseError::new(1, 1, ParseErrorKind::EmptyInput))
+        );
+        // A grid without columns would have no rows to iterate over
+        assert_eq!(
+            Grid::from_lines(&["", "1"], parse_digit).err(),
+            Some(ParseError::new(1, 1, ParseErrorKind::EmptyInput))
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = wide_grid();
+        assert_eq!(grid.row(1), &[5, 6, 7, 8]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 7]);
+        assert_eq!(
+            grid.iter_rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3, 4], &[5, 6, 7, 8]]
+        );
+        assert_eq!(
+            grid.iter_columns()
+                .map(|x| x.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec![1, 5], vec![2, 6], vec![3, 7], vec![4, 8]]
+        );
+        assert_eq!(grid.find(|x| *x == 7), Some(Position::new(1, 2)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = wide_grid();
+        let values = |positions: Vec<Position>| {
+            let mut values = positions.into_iter().map(|x| grid[x]).collect::<Vec<_>>();
+            values.sort_unstable();
+            values
+        };
+        assert_eq!(
+            values(grid.orthogonal_neighbors(Position::new(0, 0)).collect()),
+            vec![2, 5]
+        );
+        assert_eq!(
+            values(grid.adjacent_neighbors(Position::new(0, 0)).collect()),
+            vec![2, 5, 6]
+        );
+        assert_eq!(
+            values(grid.orthogonal_neighbors(Position::new(1, 3)).collect()),
+            vec![4, 7]
+        );
+        assert_eq!(
+            values(grid.adjacent_neighbors(Position::new(1, 2)).collect()),
+            vec![2, 3, 4, 6, 8]
+        );
+    }
+
+    #[test]
+    fn test_tall_grid() {
+        let grid = Grid::from_lines(&["1", "2", "3"], parse_digit).unwrap();
+        assert_eq!((grid.rows(), grid.columns()), (3, 1));
+        assert_eq!(
+            grid.adjacent_neighbors(Position::new(1, 0))
+                .collect::<Vec<_>>(),
+            vec![Position::new(0, 0), Position::new(2, 0)]
+        );
+        assert_eq!(grid.column(0).copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(wide_grid().to_string(), "1234\n5678\n");
+    }
+}
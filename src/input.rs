@@ -0,0 +1,339 @@
+//! Puzzle input shared by every day, with helpers to shape it into what each puzzle needs while
+//! keeping track of where parsing errors are.
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::grid::Grid;
+use crate::parse;
+use std::cmp::min;
+use std::str::FromStr;
+
+/// The whole text of a puzzle input, loaded once and handed to [`crate::Solution::parse`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Input {
+    text: String,
+}
+
+/// A single line of the input, along with its (1-based) number so that errors can point to it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl Input {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Line<'_>> {
+        self.text.lines().enumerate().map(|(i, text)| Line {
+            number: i + 1,
+            text,
+        })
+    }
+
+    /// Splits the input into groups of consecutive lines, separated by one or more blank lines.
+    pub fn sections(&self) -> Vec<Vec<Line<'_>>> {
+        self.lines()
+            .fold(vec![vec![]], |mut sections: Vec<Vec<_>>, line| {
+                if line.is_blank() {
+                    sections.push(vec![]);
+                } else if let Some(section) = sections.last_mut() {
+                    section.push(line);
+                }
+                sections
+            })
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .collect()
+    }
+
+    /// Parses every character of the input into a cell of a grid, see [`Grid::from_lines`].
+    pub fn char_grid<T>(
+        &self,
+        parse_cell: impl Fn(char) -> Result<T, ParseErrorKind>,
+    ) -> Result<Grid<T>, ParseError> {
+        Grid::from_lines(&self.text.lines().collect::<Vec<_>>(), parse_cell)
+    }
+
+    /// Lists every character that can't be parsed into a cell, and every row that isn't as wide as
+    /// the first one, instead of stopping at the first problem like [`Input::char_grid`].
+    pub fn char_grid_violations<T>(
+        &self,
+        parse_cell: impl Fn(char) -> Result<T, ParseErrorKind>,
+    ) -> Vec<ParseError> {
+        let Some(columns) = self
+            .text
+            .lines()
+            .next()
+            .map(|x| x.chars().count())
+            .filter(|x| *x > 0)
+        else {
+            return vec![ParseError::new(1, 1, ParseErrorKind::EmptyInput)];
+        };
+        self.lines()
+            .flat_map(|line| {
+                let found = line.text.chars().count();
+                let ragged = (found != columns).then(|| {
+                    ParseError::new(
+                        line.number,
+                        min(found, columns) + 1,
+                        ParseErrorKind::RaggedRow {
+                            expected: columns,
+                            found,
+                        },
+                    )
+                });
+                line.text
+                    .chars()
+                    .enumerate()
+                    .filter_map(|(col, x)| {
+                        let kind = parse_cell(x).err()?;
+                        Some(ParseError::new(line.number, col + 1, kind))
+                    })
+                    .chain(ragged)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Parses the comma separated list of ranges on the first line of the input, see
+    /// [`Line::csv_ranges`].
+    pub fn csv_ranges<T: FromStr + PartialOrd>(&self) -> Result<Vec<(T, T)>, ParseError> {
+        self.lines()
+            .next()
+            .ok_or(ParseError::new(1, 1, ParseErrorKind::EmptyInput))?
+            .csv_ranges()
+    }
+}
+
+impl Line<'_> {
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// Parses the whole line, pointing errors to it.
+    pub fn parse<T: FromStr<Err = ParseError>>(&self) -> Result<T, ParseError> {
+        self.parse_with(str::parse)
+    }
+
+    /// Parses the whole line with a parser that isn't aware of lines, pointing errors to it.
+    pub fn parse_with<T>(
+        &self,
+        parse: impl FnOnce(&str) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        parse(self.text).map_err(|e| e.on_line(self.number))
+    }
+
+    /// Parses the whole line as a single number.
+    pub fn number<T: FromStr>(&self) -> Result<T, ParseError> {
+        self.parse_with(|s| parse::number(s, 1))
+    }
+
+    /// Parses the whole line as an inclusive range in the form `start-end`, which can't end before
+    /// it starts.
+    pub fn range<T: FromStr + PartialOrd>(&self) -> Result<(T, T), ParseError> {
+        self.parse_with(|s| parse::ordered_range(s, 1))
+    }
+
+    /// Parses a comma separated list of ranges in the form `start-end`, e.g. `11-22,95-115`, none of
+    /// which can end before it starts.
+    pub fn csv_ranges<T: FromStr + PartialOrd>(&self) -> Result<Vec<(T, T)>, ParseError> {
+        self.csv_fields()
+            .map(|(column, s)| parse::ordered_range(s, column).map_err(|e| e.on_line(self.number)))
+            .collect()
+    }
+
+    /// Iterates over the comma separated fields of the line, along with their (1-based) column.
+    pub fn csv_fields(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.text.split(',').scan(1, |column, field| {
+            let start = *column;
+            *column += field.len() + 1;
+            Some((start, field))
+        })
+    }
+
+    /// Iterates over the whitespace separated tokens of the line, along with their (1-based) column.
+    pub fn tokens(&self) -> impl Iterator<Item = (usize, &str)> {
+        parse::tokens(self.text)
+    }
+
+    /// Parses every whitespace separated token of the line as a number.
+    pub fn integers<T: FromStr>(&self) -> impl Iterator<Item = Result<T, ParseError>> {
+        self.tokens()
+            .map(|(column, token)| parse::number(token, column).map_err(|e| e.on_line(self.number)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_lines() {
+        let input = Input::new("a\r\nb\n\nc");
+        assert_eq!(
+            input
+                .lines()
+                .map(|x| (x.number, x.text))
+                .collect::<Vec<_>>(),
+            vec![(1, "a"), (2, "b"), (3, ""), (4, "c")]
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = Input::new("\n1\n2\n\n \n3\n\n");
+        let sections = input
+            .sections()
+            .into_iter()
+            .map(|x| x.iter().map(|x| x.number).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(sections, vec![vec![2, 3], vec![6]]);
+        assert!(Input::new("").sections().is_empty());
+    }
+
+    #[test]
+    fn test_char_grid() {
+        let grid =
+            Input::new("12\n34").char_grid(|x| x.to_digit(10).ok_or(ParseErrorKind::EmptyInput));
+        assert_eq!(
+            grid.unwrap()
+                .iter_rows()
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>(),
+            [1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_char_grid_violations() {
+        let parse_cell = |x| match x {
+            '.' => Ok(()),
+            _ => Err(ParseErrorKind::UnexpectedChar {
+                found: x,
+                expected: "a dot",
+            }),
+        };
+        let violations = Input::new("...\n.x\n..x.").char_grid_violations(parse_cell);
+        assert_eq!(
+            violations
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            vec![(2, 2), (2, 3), (3, 3), (3, 4)]
+        );
+        assert!(matches!(
+            violations[1].kind,
+            ParseErrorKind::RaggedRow {
+                expected: 3,
+                found: 2
+            }
+        ));
+        assert!(
+            Input::new("..\n..")
+                .char_grid_violations(parse_cell)
+                .is_empty()
+        );
+        assert_eq!(
+            Input::new("").char_grid_violations(parse_cell),
+            vec![ParseError::new(1, 1, ParseErrorKind::EmptyInput)]
+        );
+        assert_eq!(
+            Input::new("\n").char_grid_violations(parse_cell),
+            vec![ParseError::new(1, 1, ParseErrorKind::EmptyInput)]
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        let input = Input::new("\n3-5\n5-3\n3-x");
+        let lines = input.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1].range::<u8>(), Ok((3, 5)));
+        assert_eq!(
+            lines[2].range::<u8>(),
+            Err(ParseError::new(
+                3,
+                1,
+                ParseErrorKind::ReversedRange {
+                    found: "5-3".to_string()
+                }
+            ))
+        );
+        assert_eq!(
+            lines[3].range::<u8>(),
+            Err(ParseError::new(
+                4,
+                3,
+                ParseErrorKind::UnexpectedToken {
+                    found: "x".to_string(),
+                    expected: "a number"
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_csv_ranges() {
+        assert_eq!(
+            Input::new("11-22,95-115\nignored").csv_ranges::<u64>(),
+            Ok(vec![(11, 22), (95, 115)])
+        );
+        assert_eq!(
+            Input::new("11-22,95-1x5").csv_ranges::<u64>(),
+            Err(ParseError::new(
+                1,
+                10,
+                ParseErrorKind::UnexpectedToken {
+                    found: "1x5".to_string(),
+                    expected: "a number"
+                }
+            ))
+        );
+        assert_eq!(
+            Input::new("11-22,95-15").csv_ranges::<u64>(),
+            Err(ParseError::new(
+                1,
+                7,
+                ParseErrorKind::ReversedRange {
+                    found: "95-15".to_string()
+                }
+            ))
+        );
+        assert_eq!(
+            Input::new("").csv_ranges::<u64>(),
+            Err(ParseError::new(1, 1, ParseErrorKind::EmptyInput))
+        );
+    }
+
+    #[test]
+    fn test_integers() {
+        let input = Input::new("\n 12  -3\n4 x");
+        let lines = input.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[1].tokens().collect::<Vec<_>>(),
+            vec![(2, "12"), (6, "-3")]
+        );
+        assert_eq!(
+            lines[1].integers::<i32>().collect::<Result<Vec<_>, _>>(),
+            Ok(vec![12, -3])
+        );
+        assert_eq!(
+            lines[2].integers::<i32>().collect::<Result<Vec<_>, _>>(),
+            Err(ParseError::new(
+                3,
+                3,
+                ParseErrorKind::UnexpectedToken {
+                    found: "x".to_string(),
+                    expected: "a number"
+                }
+            ))
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! Solutions to the [Advent of Code 2025](https://adventofcode.com/2025) puzzles.
+//!
+//! Each day exposes a unit struct (e.g. [`day_1::Day1`]) implementing [`Solution`], which can be
+//! used to parse a puzzle input and compute the answers to both halves of the puzzle.
+
+pub mod answers;
+pub mod client;
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod diagnostic;
+pub mod error;
+pub mod grid;
+pub mod input;
+mod parse;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+mod solution;
+pub mod submissions;
+pub mod trace;
+
+pub use error::Error;
+pub use solution::Solution;
@@ -1,38 +1,871 @@
-use clap::Parser;
+use aoc2025::Error;
+use aoc2025::answers::{KnownAnswers, Verdict};
+use aoc2025::client::{Client, ClientConfig, Outcome};
+use aoc2025::day_1;
+use aoc2025::diagnostic::Diagnostic;
+use aoc2025::error::IoAction;
+use aoc2025::input::Input;
+use aoc2025::registry::{self, SOLUTIONS, YEAR};
+use aoc2025::runner::{self, Answer, Benchmark, Part};
+use aoc2025::scaffold;
+use aoc2025::submissions::{Guess, Submissions};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
 use std::fs::File;
-use std::io::BufRead;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::SystemTime;
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::filter::{Targets, filter_fn};
+use tracing_subscriber::prelude::*;
 
-mod day_1;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-
-/// Execute the solution for a specific day
+/// Execute the solution for a specific day, or for several of them
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// Number of the Advent of Code day to choose the solution to execute
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    selection: Selection,
+    /// Path to the puzzle input, or `-` to read it from stdin. Defaults to `day_N.txt` in the
+    /// inputs directory
+    #[arg(short, long, requires = "day")]
+    input_path: Option<PathBuf>,
+    /// The puzzle input itself, handy to quickly try out example snippets
+    #[arg(long, requires = "day", conflicts_with = "input_path")]
+    input: Option<String>,
+    /// Directory where puzzle inputs are looked up by convention. Missing inputs are downloaded
+    /// into it when a session token is available
+    #[arg(long, default_value = "inputs", global = true)]
+    inputs_dir: PathBuf,
+    /// Configuration file holding the session token used to download puzzle inputs
+    #[arg(long, default_value = "aoc.toml", global = true)]
+    config: PathBuf,
+    /// Base URL of the Advent of Code website, overriding the configuration
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+    /// Logs to print, as a default level and/or `target=level` directives, e.g.
+    /// `warn,aoc2025::day_1=debug`
+    #[arg(
+        long,
+        value_name = "FILTER",
+        env = "RUST_LOG",
+        default_value = "info",
+        global = true
+    )]
+    log: Targets,
+    /// How logs are printed
+    #[arg(long, value_enum, default_value_t = LogFormat::Text, global = true)]
+    log_format: LogFormat,
+    /// Record the timings of all spans into a trace file, which can be opened with Perfetto or
+    /// `chrome://tracing`
+    #[arg(long, value_name = "PATH", global = true)]
+    chrome_trace: Option<PathBuf>,
+    /// Half of the puzzle to solve or time
+    #[arg(short, long, value_enum, default_value_t = PartSelection::Both)]
+    part: PartSelection,
+    /// How answers are printed
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Compare the answers with the known-good ones, failing on any mismatch
+    #[arg(long)]
+    check: bool,
+    /// File holding the known-good answers used by `--check`, which must exist
+    #[arg(long, default_value = "answers.toml", requires = "check")]
+    answers_path: PathBuf,
+    /// Time parsing and each half of the puzzle over the given amount of iterations, instead of
+    /// printing the answers
+    #[arg(
+        long,
+        value_name = "ITERATIONS",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        conflicts_with = "check"
+    )]
+    bench: Option<usize>,
+    /// Also write what each rotation of the dial of day 1 did to a file, to audit which ones made
+    /// up the passwords. Written as CSV, as a JSON array if the extension of the file is `.json`, or
+    /// as JSON Lines if it's `.jsonl` or `.ndjson`
+    #[arg(long, value_name = "PATH", requires = "day", conflicts_with = "bench")]
+    dial_history: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Download the puzzle inputs of the selected days into the inputs directory, unless they're
+    /// already there
+    Fetch {
+        #[command(flatten)]
+        selection: Selection,
+    },
+    /// Solve a day and submit the answer to one of its parts, unless it's already known to be wrong
+    Submit {
+        /// Number of the Advent of Code day to submit an answer for
+        #[arg(short, long)]
+        day: u8,
+        /// Part of the puzzle to submit the answer to
+        #[arg(short, long, value_parser = parse_part)]
+        part: Part,
+        /// Answer to submit instead of the computed one
+        #[arg(long)]
+        answer: Option<String>,
+        /// File recording every submitted answer. Defaults to `submissions.toml` in the inputs
+        /// directory
+        #[arg(long)]
+        submissions_path: Option<PathBuf>,
+    },
+    /// Check the puzzle inputs of the selected days against every assumption their solution makes,
+    /// listing all the violations found without solving the puzzles
+    Validate {
+        #[command(flatten)]
+        selection: Selection,
+        /// Path to the puzzle input, or `-` to read it from stdin. Defaults to `day_N.txt` in the
+        /// inputs directory
+        #[arg(short, long, requires = "day")]
+        input_path: Option<PathBuf>,
+    },
+    /// List the days that have a solution, and the parts of their puzzle it solves
+    List,
+    /// Generate the files of the solution of a new day, and register it
+    NewDay {
+        /// Number of the Advent of Code day to generate the solution of
+        #[arg(short, long)]
+        day: u8,
+        /// Root directory of the crate
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+}
+
+/// Days to work on
+#[derive(clap::Args, Debug)]
+#[group(id = "selection", required = true)]
+struct Selection {
+    /// Number of the Advent of Code day to work on
     #[arg(short, long)]
+    day: Option<u8>,
+    /// Comma separated list of days or ranges of days to work on, e.g. `1,3-5`
+    #[arg(long, value_parser = parse_days)]
+    days: Option<Days>,
+    /// Work on all the implemented days
+    #[arg(long)]
+    all: bool,
+}
+
+impl Selection {
+    fn days(&self) -> Vec<u8> {
+        match (self.day, &self.days) {
+            (Some(day), _) => vec![day],
+            (None, Some(Days(days))) => days.clone(),
+            (None, None) => registry::days(YEAR),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum PartSelection {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    /// Every implemented half of the puzzle
+    Both,
+}
+
+impl PartSelection {
+    fn parts(self) -> &'static [Part] {
+        match self {
+            PartSelection::One => &[Part::One],
+            PartSelection::Two => &[Part::Two],
+            PartSelection::Both => &[Part::One, Part::Two],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    /// Human-readable sentences for a single day, or a table for several days
+    Text,
+    /// One JSON record per line for each answer
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum LogFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON record per line for each event
+    Json,
+}
+
+#[derive(Clone, Debug)]
+struct Days(Vec<u8>);
+
+/// Where a puzzle input is read from.
+#[derive(Debug)]
+enum InputSource {
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    /// How the input is referred to in error reports.
+    fn name(&self) -> String {
+        match self {
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => "<stdin>".to_string(),
+            InputSource::Inline(_) => "<input>".to_string(),
+        }
+    }
+
+    fn read(self) -> Result<String, Error> {
+        match self {
+            InputSource::File(path) => std::fs::read_to_string(&path).map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => Error::InputNotFound(path),
+                _ => Error::io(IoAction::Read, path, e),
+            }),
+            InputSource::Stdin => std::io::read_to_string(std::io::stdin())
+                .map_err(|e| Error::io(IoAction::Read, self.name(), e)),
+            InputSource::Inline(input) => Ok(input),
+        }
+    }
+}
+
+/// Parses a selection of days such as `1,3-5` into the list of days it contains.
+fn parse_days(s: &str) -> Result<Days, String> {
+    let mut days = vec![];
+    for item in s.split(',').map(str::trim) {
+        let (start, end) = item.split_once('-').unwrap_or((item, item));
+        let start = start.parse::<u8>().map_err(|e| format!("{item}: {e}"))?;
+        let end = end.parse::<u8>().map_err(|e| format!("{item}: {e}"))?;
+        if start > end {
+            return Err(format!("{item}: the range is empty"));
+        }
+        days.extend(start..=end);
+    }
+    days.sort_unstable();
+    days.dedup();
+    Ok(Days(days))
+}
+
+fn parse_part(s: &str) -> Result<Part, String> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("{s}: expected either 1 or 2")),
+    }
+}
+
+/// An answer, along with the outcome of comparing it with the known-good one.
+#[derive(Serialize)]
+struct CheckedAnswer<'a> {
+    #[serde(flatten)]
+    answer: &'a Answer,
+    status: Verdict,
+    expected: Option<String>,
+}
+
+/// Sets up the logs, which are printed to stderr, and the trace file when one is requested.
+/// The returned guard must be kept alive until the program exits, so that the trace file is complete.
+fn init_tracing(args: &Args) -> std::io::Result<Option<FlushGuard>> {
+    let logs = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+    let logs = match args.log_format {
+        LogFormat::Text => logs.boxed(),
+        LogFormat::Json => logs.json().boxed(),
+    };
+    let (trace, guard) = match &args.chrome_trace {
+        Some(path) => {
+            let (layer, guard) = ChromeLayerBuilder::new()
+                .writer(File::create(path)?)
+                .include_args(true)
+                .build();
+            // Spans are recorded regardless of the level of the logs
+            let layer = layer.with_filter(filter_fn(|metadata| metadata.is_span()));
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+    tracing_subscriber::registry()
+        .with(logs.with_filter(args.log.clone()))
+        .with(trace)
+        .init();
+    Ok(guard)
+}
+
+/// Prints an error that occurred while working on a day, along with a hint on how to get past it
+/// when there's one.
+fn report(day: u8, e: &Error) {
+    match e {
+        Error::UnimplementedDay(_) => {
+            eprintln!("error: {e}");
+            eprintln!(
+                "hint: run `list` to see the implemented days, or `new-day --day {day}` to start \
+                solving this one"
+            );
+        }
+        _ => eprintln!("error: day {day}: {e}"),
+    }
+}
+
+/// Prints an error that occurred while solving a day with the given input, pointing at the
+/// offending location of the input when it couldn't be parsed.
+fn report_input(day: u8, name: &str, input: &str, e: &Error) {
+    match e {
+        Error::Parse(e) => eprint!("{}", Diagnostic::new(name, input, e)),
+        _ => report(day, e),
+    }
+}
+
+/// Builds the client to the Advent of Code website out of the configuration file, the environment
+/// and the command line, in increasing order of precedence.
+fn client(args: &Args) -> Result<Client, Error> {
+    let mut config = ClientConfig::load(&args.config)?;
+    if let Some(base_url) = &args.base_url {
+        config.base_url = Some(base_url.clone());
+    }
+    Client::from_config(&config)
+}
+
+/// Returns the path where the input of a day is looked up by convention, downloading it first when
+/// it's missing and a client is available.
+fn cached_input(inputs_dir: &Path, client: Option<&Client>, day: u8) -> Result<PathBuf, Error> {
+    let path = inputs_dir.join(format!("day_{day}.txt"));
+    if let Some(client) = client
+        && client.fetch_input_to(day, &path)?
+    {
+        eprintln!("Fetched the input of day {day} into {}", path.display());
+    }
+    Ok(path)
+}
+
+/// Resolves the selected days to the inputs to solve them with, returning whether all of them
+/// could be resolved. Days without an input are skipped, unless a single day was explicitly requested.
+fn select_inputs(args: &Args, client: Option<&Client>) -> (Vec<(u8, InputSource)>, bool) {
+    if let Some(day) = args.selection.day {
+        // Checked before downloading the input, which a day without a solution doesn't need
+        if let Err(e) = registry::find(YEAR, day) {
+            report(day, &e);
+            return (vec![], false);
+        }
+        let source = match (&args.input, &args.input_path) {
+            (Some(input), _) => InputSource::Inline(input.clone()),
+            (None, Some(path)) if path.as_os_str() == "-" => InputSource::Stdin,
+            (None, Some(path)) => InputSource::File(path.clone()),
+            (None, None) => match cached_input(&args.inputs_dir, client, day) {
+                Ok(path) => InputSource::File(path),
+                Err(e) => {
+                    report(day, &e);
+                    return (vec![], false);
+                }
+            },
+        };
+        return (vec![(day, source)], true);
+    }
+
+    let mut inputs = vec![];
+    let mut success = true;
+    for day in args.selection.days() {
+        if let Err(e) = registry::find(YEAR, day) {
+            report(day, &e);
+            success = false;
+            continue;
+        }
+        let path = match cached_input(&args.inputs_dir, client, day) {
+            Ok(path) => path,
+            Err(e) => {
+                report(day, &e);
+                success = false;
+                continue;
+            }
+        };
+        if !path.exists() {
+            eprintln!("Skipping day {day}: no input found at {}", path.display());
+            continue;
+        }
+        inputs.push((day, InputSource::File(path)));
+    }
+    (inputs, success)
+}
+
+/// Runs the given solver against each of the selected inputs, returning all of its results and
+/// whether every day could be solved.
+fn run_selection<T>(
+    args: &Args,
+    client: Option<&Client>,
+    solver: impl Fn(u8, &str) -> Result<Vec<T>, Error>,
+) -> (Vec<T>, bool) {
+    let (inputs, mut success) = select_inputs(args, client);
+    let mut results = vec![];
+    for (day, source) in inputs {
+        let name = source.name();
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(e) => {
+                report(day, &e);
+                success = false;
+                continue;
+            }
+        };
+        match solver(day, &input) {
+            Ok(day_results) => results.extend(day_results),
+            Err(e) => {
+                report_input(day, &name, &input, &e);
+                success = false;
+            }
+        }
+    }
+    (results, success)
+}
+
+/// Prints the rows as a table, padding each column to its widest cell.
+fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let widths = (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain([headers[i].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let print_row = |row: &[&str]| {
+        let cells = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>();
+        println!("{}", cells.join("  ").trim_end());
+    };
+    print_row(headers);
+    for row in rows {
+        print_row(&row.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+}
+
+/// Prints one JSON record per item, each on its own line.
+fn print_json<T: Serialize>(items: &[T]) {
+    for item in items {
+        println!(
+            "{}",
+            serde_json::to_string(item).expect("Records are always serializable")
+        );
+    }
+}
+
+fn print_answers(answers: &[Answer], format: Format, single_day: bool) {
+    match format {
+        Format::Text if single_day => {
+            for answer in answers {
+                println!("{}: {}", answer.label, answer.value);
+            }
+        }
+        Format::Text => print_table(
+            &["Day", "Part", "Answer", "Time"],
+            &answers
+                .iter()
+                .map(|x| {
+                    vec![
+                        x.day.to_string(),
+                        x.part.to_string(),
+                        x.value.clone(),
+                        format!("{:.2?}", x.elapsed),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ),
+        Format::Json => print_json(answers),
+    }
+}
+
+fn print_benchmarks(benchmarks: &[Benchmark], format: Format) {
+    match format {
+        Format::Text => print_table(
+            &["Day", "Stage", "Min", "Median", "Mean", "Stddev"],
+            &benchmarks
+                .iter()
+                .map(|x| {
+                    vec![
+                        x.day.to_string(),
+                        x.stage.to_string(),
+                        format!("{:.2?}", x.stats.min),
+                        format!("{:.2?}", x.stats.median),
+                        format!("{:.2?}", x.stats.mean),
+                        format!("{:.2?}", x.stats.stddev),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ),
+        Format::Json => print_json(benchmarks),
+    }
+}
+
+/// Prints every registered solution, along with the parts of the puzzle it solves.
+fn print_solutions() {
+    print_table(
+        &["Year", "Day", "Title", "Parts"],
+        &SOLUTIONS
+            .iter()
+            .map(|x| {
+                let parts = x.parts.iter().map(Part::to_string).collect::<Vec<_>>();
+                vec![
+                    x.year.to_string(),
+                    x.day.to_string(),
+                    x.title.to_string(),
+                    if parts.is_empty() {
+                        "none".to_string()
+                    } else {
+                        parts.join(", ")
+                    },
+                ]
+            })
+            .collect::<Vec<_>>(),
+    );
+}
+
+/// Prints the outcome of checking each answer, returning whether all known answers matched.
+fn print_checked_answers(answers: &[Answer], known: &KnownAnswers, format: Format) -> bool {
+    let checked = answers
+        .iter()
+        .map(|answer| CheckedAnswer {
+            answer,
+            status: known.check(answer),
+            expected: known.get(answer.day, answer.part),
+        })
+        .collect::<Vec<_>>();
+    match format {
+        Format::Text => print_table(
+            &["Day", "Part", "Answer", "Expected", "Status"],
+            &checked
+                .iter()
+                .map(|x| {
+                    vec![
+                        x.answer.day.to_string(),
+                        x.answer.part.to_string(),
+                        x.answer.value.clone(),
+                        x.expected.clone().unwrap_or_default(),
+                        x.status.to_string(),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ),
+        Format::Json => print_json(&checked),
+    }
+    checked.iter().all(|x| x.status != Verdict::Fail)
+}
+
+/// Downloads the inputs of the selected days, returning whether all of them are now available.
+fn fetch(args: &Args, selection: &Selection) -> bool {
+    let client = match client(args) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return false;
+        }
+    };
+    let mut success = true;
+    for day in selection.days() {
+        let path = args.inputs_dir.join(format!("day_{day}.txt"));
+        match client.fetch_input_to(day, &path) {
+            Ok(true) => println!("Fetched the input of day {day} into {}", path.display()),
+            Ok(false) => println!("The input of day {day} is already at {}", path.display()),
+            Err(e) => {
+                report(day, &e);
+                success = false;
+            }
+        }
+    }
+    success
+}
+
+/// Checks the inputs of the selected days, printing every violation found in them. Returns whether
+/// all of them are valid.
+fn validate(args: &Args, selection: &Selection, input_path: Option<&Path>) -> bool {
+    let mut valid = true;
+    for day in selection.days() {
+        let entry = match registry::find(YEAR, day) {
+            Ok(entry) => entry,
+            Err(e) => {
+                report(day, &e);
+                valid = false;
+                continue;
+            }
+        };
+        let source = match input_path {
+            Some(path) if path.as_os_str() == "-" => InputSource::Stdin,
+            Some(path) => InputSource::File(path.to_path_buf()),
+            None => InputSource::File(args.inputs_dir.join(format!("day_{day}.txt"))),
+        };
+        let name = source.name();
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(e) => {
+                report(day, &e);
+                valid = false;
+                continue;
+            }
+        };
+        let violations = entry.validate(&input);
+        for violation in &violations {
+            eprint!("{}", Diagnostic::new(&name, &input, violation));
+        }
+        if violations.is_empty() {
+            println!("Day {day}: {name} is valid");
+        } else {
+            println!("Day {day}: found {} problems in {name}", violations.len());
+            valid = false;
+        }
+    }
+    valid
+}
+
+/// Writes the history of the rotations of the dial of day 1, see `--dial-history`.
+fn write_dial_history(input: &str, path: &Path) -> Result<(), Error> {
+    let history = day_1::rotation_history(&Input::new(input))?;
+    let write = || {
+        let mut file = BufWriter::new(File::create(path)?);
+        match path.extension() {
+            Some(x) if x == "json" => day_1::write_json(&history, &mut file)?,
+            Some(x) if x == "jsonl" || x == "ndjson" => {
+                day_1::write_json_lines(&history, &mut file)?
+            }
+            _ => day_1::write_csv(&history, &mut file)?,
+        }
+        file.flush()
+    };
+    write().map_err(|e| Error::io(IoAction::Write, path, e))
+}
+
+/// Computes the answer to a part of the puzzle of a day, downloading its input if needed.
+fn compute_answer(args: &Args, client: &Client, day: u8, part: Part) -> Result<String, Error> {
+    registry::find(YEAR, day)?;
+    let path = cached_input(&args.inputs_dir, Some(client), day)?;
+    let input = InputSource::File(path).read()?;
+    let answer = runner::solve_day(day, &input, &[part])?
+        .pop()
+        .expect("The requested part is always solved");
+    Ok(answer.value)
+}
+
+/// Submits the answer to a part of the puzzle of a day, returning whether it was correct.
+/// Every submission is recorded, and answers that are known to be wrong are never submitted.
+fn submit(
+    args: &Args,
     day: u8,
-    #[arg(short, long)]
-    input_path: String,
+    part: Part,
+    answer: Option<&str>,
+    submissions_path: &Path,
+) -> Result<bool, Error> {
+    let client = client(args)?;
+    let answer = match answer {
+        Some(answer) => answer.to_string(),
+        None => compute_answer(args, &client, day, part)?,
+    };
+    let mut submissions = Submissions::load(submissions_path)?;
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("The clock is set after the Unix epoch")
+        .as_secs();
+    if let Some(refusal) = submissions.check(day, part, &answer, now) {
+        eprintln!("Not submitting {answer} to day {day} part {part}: {refusal}");
+        return Ok(false);
+    }
+    let outcome = client.submit_answer(day, part, &answer)?;
+    submissions.record(
+        day,
+        part,
+        Guess {
+            answer: answer.clone(),
+            outcome,
+            submitted_at: now,
+        },
+    );
+    submissions.save(submissions_path)?;
+    println!("Submitted {answer} to day {day} part {part}: {outcome}");
+    Ok(outcome == Outcome::Correct)
 }
-fn main() {
-    tracing_subscriber::fmt::init();
+
+fn main() -> ExitCode {
     let args = Args::parse();
-    let file = File::open(args.input_path).unwrap();
-    let input_lines = std::io::BufReader::new(file).lines();
-    match args.day {
-        1 => day_1::day_1_solution(input_lines),
-        2 => day_2::day_2_solution(input_lines),
-        3 => day_3::day_3_solution(input_lines),
-        4 => day_4::day_4_solution(input_lines),
-        5 => day_5::day_5_solution(input_lines),
-        6 => day_6::day_6_solution(input_lines),
-        7 => day_7::day_7_solution(input_lines),
-        _ => panic!("Day {} not implemented yet", args.day),
+    let _guard = match init_tracing(&args) {
+        Ok(guard) => guard,
+        Err(e) => {
+            eprintln!("error: couldn't create the trace file: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match &args.command {
+        Some(Command::Fetch { selection }) => {
+            return if fetch(&args, selection) {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            };
+        }
+        Some(Command::Submit {
+            day,
+            part,
+            answer,
+            submissions_path,
+        }) => {
+            let submissions_path = submissions_path
+                .clone()
+                .unwrap_or_else(|| args.inputs_dir.join("submissions.toml"));
+            return match submit(&args, *day, *part, answer.as_deref(), &submissions_path) {
+                Ok(true) => ExitCode::SUCCESS,
+                Ok(false) => ExitCode::FAILURE,
+                Err(e) => {
+                    report(*day, &e);
+                    ExitCode::FAILURE
+                }
+            };
+        }
+        Some(Command::NewDay { day, root }) => {
+            return match scaffold::new_day(root, *day) {
+                Ok(files) => {
+                    for file in files {
+                        println!("Wrote {}", file.display());
+                    }
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("error: {e}");
+                    ExitCode::FAILURE
+                }
+            };
+        }
+        Some(Command::Validate {
+            selection,
+            input_path,
+        }) => {
+            return if validate(&args, selection, input_path.as_deref()) {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            };
+        }
+        Some(Command::List) => {
+            print_solutions();
+            return ExitCode::SUCCESS;
+        }
+        None => {}
+    }
+
+    // Inputs are only downloaded automatically when a session token has been set up
+    let client = match client(&args) {
+        Ok(client) => Some(client),
+        Err(Error::MissingSession) => None,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = client.as_ref();
+    if let Some(iterations) = args.bench {
+        let (benchmarks, success) = run_selection(&args, client, |day, input| {
+            runner::bench_day(day, input, iterations, args.part.parts())
+        });
+        print_benchmarks(&benchmarks, args.format);
+        return if success {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    }
+
+    if args.dial_history.is_some() && args.selection.day != Some(1) {
+        eprintln!("error: the history of the dial can only be written for day 1");
+        return ExitCode::FAILURE;
+    }
+    let (answers, mut success) = run_selection(&args, client, |day, input| {
+        if let Some(path) = &args.dial_history {
+            write_dial_history(input, path)?;
+        }
+        runner::solve_day(day, input, args.part.parts())
+    });
+    if args.check {
+        match KnownAnswers::load(&args.answers_path) {
+            Ok(known) => success &= print_checked_answers(&answers, &known, args.format),
+            Err(e) => {
+                match e {
+                    // The path is already part of the message
+                    Error::Io { .. } => eprintln!("error: {e}"),
+                    _ => eprintln!("error: {}: {e}", args.answers_path.display()),
+                }
+                success = false;
+            }
+        }
+    } else {
+        print_answers(&answers, args.format, args.selection.day.is_some());
+    }
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("1,3-5").unwrap().0, vec![1, 3, 4, 5]);
+        assert_eq!(parse_days("4-5, 1, 5").unwrap().0, vec![1, 4, 5]);
+        assert!(parse_days("5-3").is_err());
+        assert!(parse_days("1,x").is_err());
+    }
+
+    #[test]
+    fn test_log_arguments() {
+        let args = Args::try_parse_from([
+            "aoc2025",
+            "--day",
+            "1",
+            "--log",
+            "warn,aoc2025::day_1=debug",
+            "--log-format",
+            "json",
+        ])
+        .unwrap();
+        assert!(
+            args.log
+                .would_enable("aoc2025::day_1::dial", &tracing::Level::DEBUG)
+        );
+        assert!(
+            !args
+                .log
+                .would_enable("aoc2025::day_2", &tracing::Level::INFO)
+        );
+        assert!(Args::try_parse_from(["aoc2025", "--day", "1", "--log", "aoc2025=loud"]).is_err());
+    }
+
+    #[test]
+    fn test_unimplemented_day_is_not_fetched() {
+        let args =
+            Args::try_parse_from(["aoc2025", "--day", "9", "--inputs-dir", "missing-inputs"])
+                .unwrap();
+        // Nothing listens on the discard port, so fetching the input would fail
+        let client = Client::new("http://127.0.0.1:9", "secret");
+        assert!(matches!(
+            compute_answer(&args, &client, 9, Part::One),
+            Err(Error::UnimplementedDay(9))
+        ));
+    }
+
+    #[test]
+    fn test_bench_iterations() {
+        let args = Args::try_parse_from(["aoc2025", "--day", "1", "--bench", "3"]).unwrap();
+        assert_eq!(args.bench, Some(3));
+        assert!(Args::try_parse_from(["aoc2025", "--day", "1", "--bench", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_part() {
+        assert_eq!(parse_part("1"), Ok(Part::One));
+        assert_eq!(parse_part("2"), Ok(Part::Two));
+        assert!(parse_part("3").is_err());
     }
 }
@@ -0,0 +1,109 @@
+//! Small parsing helpers shared by the solutions of different days.
+
+use crate::error::{ParseError, ParseErrorKind};
+use std::str::FromStr;
+
+/// Parses a number out of a token found at the given (1-based) column of a line.
+pub(crate) fn number<T: FromStr>(token: &str, column: usize) -> Result<T, ParseError> {
+    token.parse().map_err(|_| {
+        ParseError::new(
+            1,
+            column,
+            ParseErrorKind::UnexpectedToken {
+                found: token.to_string(),
+                expected: "a number",
+            },
+        )
+    })
+}
+
+/// Parses an inclusive range in the form `start-end`, found at the given (1-based) column of a line.
+pub(crate) fn range<T: FromStr>(s: &str, column: usize) -> Result<(T, T), ParseError> {
+    let (start, end) = s.split_once('-').ok_or(ParseError::new(
+        1,
+        column + s.len(),
+        ParseErrorKind::UnexpectedEndOfLine {
+            expected: "a '-' separating the start and end of the range",
+        },
+    ))?;
+    Ok((
+        number(start, column)?,
+        number(end, column + start.len() + 1)?,
+    ))
+}
+
+/// Parses an inclusive range like [`range`], additionally rejecting ranges that end before they start.
+pub(crate) fn ordered_range<T: FromStr + PartialOrd>(
+    s: &str,
+    column: usize,
+) -> Result<(T, T), ParseError> {
+    let (start, end) = range(s, column)?;
+    if start > end {
+        return Err(ParseError::new(
+            1,
+            column,
+            ParseErrorKind::ReversedRange {
+                found: s.to_string(),
+            },
+        ));
+    }
+    Ok((start, end))
+}
+
+/// Splits a line into whitespace separated tokens, along with their (1-based) column.
+pub(crate) fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split_whitespace()
+        .map(move |token| (token.as_ptr() as usize - line.as_ptr() as usize + 1, token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range() {
+        assert_eq!(range::<u64>("11-22", 1), Ok((11, 22)));
+        assert_eq!(
+            range::<u64>("11-2x", 5),
+            Err(ParseError::new(
+                1,
+                8,
+                ParseErrorKind::UnexpectedToken {
+                    found: "2x".to_string(),
+                    expected: "a number"
+                }
+            ))
+        );
+        assert!(matches!(
+            range::<u64>("11", 1),
+            Err(ParseError {
+                column: 3,
+                kind: ParseErrorKind::UnexpectedEndOfLine { .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_ordered_range() {
+        assert_eq!(ordered_range::<u64>("5-5", 1), Ok((5, 5)));
+        assert_eq!(
+            ordered_range::<u64>("22-11", 3),
+            Err(ParseError::new(
+                1,
+                3,
+                ParseErrorKind::ReversedRange {
+                    found: "22-11".to_string()
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_tokens() {
+        assert_eq!(
+            tokens("  1 23   *").collect::<Vec<_>>(),
+            vec![(3, "1"), (5, "23"), (10, "*")]
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Central registry of the solutions, so they can be listed and picked at runtime.
+
+use crate::error::ParseError;
+use crate::runner::{self, Answer, Benchmark, Part};
+use crate::{Error, Solution};
+
+/// Year of the event the solutions are written for.
+pub const YEAR: u16 = 2025;
+
+/// Type-erased [`runner::solve`]
+type SolveFn = fn(u8, &str, &[Part]) -> Result<Vec<Answer>, Error>;
+/// Type-erased [`runner::bench`]
+type BenchFn = fn(u8, &str, usize, &[Part]) -> Result<Vec<Benchmark>, Error>;
+/// Type-erased [`runner::validate`]
+type ValidateFn = fn(u8, &str) -> Vec<ParseError>;
+
+/// A registered solution, along with its metadata and its type-erased entry points.
+#[derive(Debug)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub parts: &'static [Part],
+    solve: SolveFn,
+    bench: BenchFn,
+    validate: ValidateFn,
+}
+
+impl Entry {
+    const fn of<S: Solution>(year: u16, day: u8) -> Self {
+        Self {
+            year,
+            day,
+            title: S::TITLE,
+            parts: S::IMPLEMENTED_PARTS,
+            solve: runner::solve::<S>,
+            bench: runner::bench::<S>,
+            validate: runner::validate::<S>,
+        }
+    }
+
+    /// Parses the input and solves the requested halves of the puzzle, see [`runner::solve`].
+    pub fn solve(&self, input: &str, parts: &[Part]) -> Result<Vec<Answer>, Error> {
+        (self.solve)(self.day, input, parts)
+    }
+
+    /// Times parsing and the requested halves of the puzzle over the given amount of iterations,
+    /// see [`runner::bench`].
+    pub fn bench(
+        &self,
+        input: &str,
+        iterations: usize,
+        parts: &[Part],
+    ) -> Result<Vec<Benchmark>, Error> {
+        (self.bench)(self.day, input, iterations, parts)
+    }
+
+    /// Lists every violation of the assumptions of the solution found in the input, see
+    /// [`runner::validate`].
+    pub fn validate(&self, input: &str) -> Vec<ParseError> {
+        (self.validate)(self.day, input)
+    }
+}
+
+/// Generates the table of all the registered solutions.
+macro_rules! registry {
+    ($(($year:literal, $day:literal) => $solution:ty),* $(,)?) => {
+        /// Every registered solution, sorted by year and day
+        pub static SOLUTIONS: &[Entry] = &[$(Entry::of::<$solution>($year, $day)),*];
+    };
+}
+
+// The `new-day` generator appends new days to this list
+registry! {
+    (2025, 1) => crate::day_1::Day1,
+    (2025, 2) => crate::day_2::Day2,
+    (2025, 3) => crate::day_3::Day3,
+    (2025, 4) => crate::day_4::Day4,
+    (2025, 5) => crate::day_5::Day5,
+    (2025, 6) => crate::day_6::Day6,
+    (2025, 7) => crate::day_7::Day7,
+}
+
+/// Looks up the solution of the given day, failing if the day hasn't been implemented yet.
+pub fn find(year: u16, day: u8) -> Result<&'static Entry, Error> {
+    SOLUTIONS
+        .iter()
+        .find(|x| x.year == year && x.day == day)
+        .ok_or(Error::UnimplementedDay(day))
+}
+
+/// Lists the days of the given year that have a solution, in ascending order.
+pub fn days(year: u16) -> Vec<u8> {
+    SOLUTIONS
+        .iter()
+        .filter(|x| x.year == year)
+        .map(|x| x.day)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_sorted() {
+        assert!(
+            SOLUTIONS
+                .windows(2)
+                .all(|x| (x[0].year, x[0].day) < (x[1].year, x[1].day))
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        let entry = find(2025, 4).unwrap();
+        assert_eq!(entry.title, "Printing Department");
+        assert_eq!(entry.parts, &[Part::One, Part::Two]);
+        assert!(matches!(find(2025, 26), Err(Error::UnimplementedDay(26))));
+        assert!(matches!(find(2024, 1), Err(Error::UnimplementedDay(1))));
+        assert_eq!(days(2025)[..7], [1, 2, 3, 4, 5, 6, 7]);
+        assert!(days(2024).is_empty());
+    }
+}
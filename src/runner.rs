@@ -0,0 +1,367 @@
+//! Timed execution of the solutions, and of the registered ones picked at runtime.
+
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::registry::{self, YEAR};
+use crate::{Error, Solution};
+use serde::{Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use tracing::info_span;
+
+/// Identifies one of the two halves of a puzzle.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[serde(into = "u8")]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// The answer to one half of a puzzle, along with how long it took to compute it.
+/// When serialized, the elapsed time is expressed in seconds.
+#[derive(Debug, Serialize)]
+pub struct Answer {
+    pub day: u8,
+    pub part: Part,
+    pub label: &'static str,
+    #[serde(rename = "answer")]
+    pub value: String,
+    #[serde(serialize_with = "serialize_seconds")]
+    pub elapsed: Duration,
+}
+
+fn serialize_seconds<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+/// Identifies a stage of a solution that can be timed on its own.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Parse,
+    #[serde(rename = "part_1")]
+    Part1,
+    #[serde(rename = "part_2")]
+    Part2,
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part1 => write!(f, "part 1"),
+            Stage::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+/// Summary statistics of the time taken by several iterations of the same stage.
+/// When serialized, all durations are expressed in seconds.
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct Stats {
+    pub iterations: usize,
+    #[serde(serialize_with = "serialize_seconds")]
+    pub min: Duration,
+    #[serde(serialize_with = "serialize_seconds")]
+    pub median: Duration,
+    #[serde(serialize_with = "serialize_seconds")]
+    pub mean: Duration,
+    /// Sample standard deviation, which is zero for a single iteration
+    #[serde(serialize_with = "serialize_seconds")]
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of a non-empty set of samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(
+            !samples.is_empty(),
+            "Can't compute statistics of no samples"
+        );
+        samples.sort_unstable();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = samples
+            .iter()
+            .map(|x| (x.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / (n.saturating_sub(1).max(1)) as f64;
+        Self {
+            iterations: n,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Timing statistics of a single stage of the solution of a day.
+#[derive(Debug, Serialize)]
+pub struct Benchmark {
+    pub day: u8,
+    pub stage: Stage,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+/// Times a single half of the puzzle.
+fn answer<T: Display>(
+    day: u8,
+    part: Part,
+    label: &'static str,
+    solve: impl FnOnce() -> Result<T, Error>,
+) -> Result<Answer, Error> {
+    let _span = info_span!("solve", day, %part).entered();
+    let start = Instant::now();
+    let value = solve()?.to_string();
+    Ok(Answer {
+        day,
+        part,
+        label,
+        value,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Keeps the requested halves of the puzzle that have been implemented, failing if none of them has.
+fn implemented_parts<S: Solution>(day: u8, parts: &[Part]) -> Result<Vec<Part>, Error> {
+    let implemented = parts
+        .iter()
+        .copied()
+        .filter(|x| S::IMPLEMENTED_PARTS.contains(x))
+        .collect::<Vec<_>>();
+    match parts.first() {
+        Some(&part) if implemented.is_empty() => Err(Error::UnimplementedPart { day, part }),
+        _ => Ok(implemented),
+    }
+}
+
+/// Parses the input and solves the requested halves of the puzzle, timing each of them.
+/// Halves that haven't been implemented are skipped, unless none of the requested ones has.
+pub fn solve<S: Solution>(day: u8, input: &str, parts: &[Part]) -> Result<Vec<Answer>, Error> {
+    let parts = implemented_parts::<S>(day, parts)?;
+    let input = Input::new(input);
+    let input = info_span!("parse", day).in_scope(|| S::parse(&input))?;
+    parts
+        .into_iter()
+        .map(|part| match part {
+            Part::One => answer(day, part, S::PART_1_LABEL, || S::part_1(&input)),
+            Part::Two => answer(day, part, S::PART_2_LABEL, || S::part_2(&input)),
+        })
+        .collect()
+}
+
+/// Times parsing and the requested halves of the puzzle separately over the given amount of
+/// iterations. Halves are picked as in [`solve`].
+/// Panics if there are no iterations.
+pub fn bench<S: Solution>(
+    day: u8,
+    input: &str,
+    iterations: usize,
+    parts: &[Part],
+) -> Result<Vec<Benchmark>, Error> {
+    fn time<T>(iterations: usize, mut f: impl FnMut() -> Result<T, Error>) -> Result<Stats, Error> {
+        let samples = (0..iterations)
+            .map(|_| {
+                let start = Instant::now();
+                std::hint::black_box(f()?);
+                Ok(start.elapsed())
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(Stats::from_samples(samples))
+    }
+
+    let parts = implemented_parts::<S>(day, parts)?;
+    let input = Input::new(input);
+    let mut benchmarks = vec![Benchmark {
+        day,
+        stage: Stage::Parse,
+        stats: time(iterations, || S::parse(&input))?,
+    }];
+    let input = S::parse(&input)?;
+    for part in parts {
+        let (stage, stats) = match part {
+            Part::One => (Stage::Part1, time(iterations, || S::part_1(&input))?),
+            Part::Two => (Stage::Part2, time(iterations, || S::part_2(&input))?),
+        };
+        benchmarks.push(Benchmark { day, stage, stats });
+    }
+    Ok(benchmarks)
+}
+
+/// Lists every violation of the assumptions of the solution found in the input, in the order they
+/// appear in it.
+pub fn validate<S: Solution>(day: u8, input: &str) -> Vec<ParseError> {
+    let mut violations = info_span!("validate", day).in_scope(|| S::validate(&Input::new(input)));
+    violations.sort_by_key(|x| (x.line, x.column));
+    violations
+}
+
+/// Solves the requested halves of the puzzle of the given day, failing if the day hasn't been
+/// implemented yet.
+pub fn solve_day(day: u8, input: &str, parts: &[Part]) -> Result<Vec<Answer>, Error> {
+    registry::find(YEAR, day)?.solve(input, parts)
+}
+
+/// Benchmarks the requested halves of the solution of the given day, failing if the day hasn't
+/// been implemented yet.
+pub fn bench_day(
+    day: u8,
+    input: &str,
+    iterations: usize,
+    parts: &[Part],
+) -> Result<Vec<Benchmark>, Error> {
+    registry::find(YEAR, day)?.bench(input, iterations, parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    #[test]
+    fn test_solve_day() {
+        let answers = solve_day(1, include_str!("day_1/example.txt"), &BOTH).unwrap();
+        assert_eq!(
+            answers
+                .iter()
+                .map(|x| (x.day, x.part, x.value.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, Part::One, "3"), (1, Part::Two, "6")]
+        );
+        assert!(matches!(
+            solve_day(26, "", &BOTH),
+            Err(Error::UnimplementedDay(26))
+        ));
+    }
+
+    /// A solution of which only the first half has been implemented.
+    struct HalfSolved;
+
+    impl Solution for HalfSolved {
+        type Input = usize;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        const TITLE: &'static str = "Half solved";
+        const IMPLEMENTED_PARTS: &'static [Part] = &[Part::One];
+        const PART_1_LABEL: &'static str = "Number of lines";
+        const PART_2_LABEL: &'static str = "Unknown";
+
+        fn parse(input: &Input) -> Result<Self::Input, Error> {
+            Ok(input.lines().count())
+        }
+
+        fn part_1(input: &Self::Input) -> Result<Self::Part1, Error> {
+            Ok(*input)
+        }
+
+        fn part_2(_: &Self::Input) -> Result<Self::Part2, Error> {
+            unreachable!("Part 2 is never run")
+        }
+    }
+
+    #[test]
+    fn test_solve_parts() {
+        let parts = |answers: Vec<Answer>| answers.iter().map(|x| x.part).collect::<Vec<_>>();
+        let example = include_str!("day_1/example.txt");
+        assert_eq!(
+            parts(solve_day(1, example, &[Part::Two]).unwrap()),
+            vec![Part::Two]
+        );
+        assert_eq!(
+            parts(solve::<HalfSolved>(0, "a\nb", &BOTH).unwrap()),
+            vec![Part::One]
+        );
+        assert!(matches!(
+            solve::<HalfSolved>(0, "a\nb", &[Part::Two]),
+            Err(Error::UnimplementedPart {
+                day: 0,
+                part: Part::Two
+            })
+        ));
+        assert_eq!(
+            bench::<HalfSolved>(0, "a", 1, &BOTH)
+                .unwrap()
+                .iter()
+                .map(|x| x.stage)
+                .collect::<Vec<_>>(),
+            vec![Stage::Parse, Stage::Part1]
+        );
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let benchmarks = bench_day(7, include_str!("day_7/example.txt"), 3, &BOTH).unwrap();
+        assert_eq!(
+            benchmarks
+                .iter()
+                .map(|x| (x.stage, x.stats.iterations))
+                .collect::<Vec<_>>(),
+            vec![(Stage::Parse, 3), (Stage::Part1, 3), (Stage::Part2, 3)]
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(
+            [4, 1, 3, 2]
+                .into_iter()
+                .map(Duration::from_millis)
+                .collect(),
+        );
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        // Sample variance of 1, 2, 3 and 4 ms is 5/3 ms^2
+        assert_eq!(
+            stats.stddev.as_nanos(),
+            Duration::from_secs_f64((5.0_f64 / 3.0).sqrt() / 1000.0).as_nanos()
+        );
+        assert_eq!(
+            Stats::from_samples(vec![Duration::from_millis(1)]).stddev,
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_serialize_answer() {
+        let answer = Answer {
+            day: 7,
+            part: Part::Two,
+            label: "Number of timelines",
+            value: "40".to_string(),
+            elapsed: Duration::from_millis(1500),
+        };
+        assert_eq!(
+            serde_json::to_string(&answer).unwrap(),
+            r#"{"day":7,"part":2,"label":"Number of timelines","answer":"40","elapsed":1.5}"#
+        );
+    }
+}
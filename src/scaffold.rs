@@ -0,0 +1,182 @@
+//! Generator of the files needed to solve a new day, following the layout of the existing ones.
+
+use crate::Error;
+use crate::error::IoAction;
+use crate::registry::YEAR;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DAY_TEMPLATE: &str = include_str!("scaffold/day.rs.template");
+const PUZZLE_TEMPLATE: &str = include_str!("scaffold/puzzle.rs.template");
+/// Placeholder replaced by the number of the day in the templates
+const PLACEHOLDER: &str = "__DAY__";
+
+/// Creates the solution of a new day in the crate at the given root, and registers it so that it
+/// can be run straight away. Returns the paths of all the files that were created or modified.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, Error> {
+    let src = root.join("src");
+    let day_path = src.join(format!("day_{day}.rs"));
+    let module_dir = src.join(format!("day_{day}"));
+    if day_path.exists() || module_dir.exists() {
+        return Err(Error::DayAlreadyExists(day));
+    }
+
+    // Registrations are prepared before writing anything, so that a failure leaves no trace
+    let lib_path = src.join("lib.rs");
+    let lib =
+        register_module(&read(&lib_path)?, day)?.ok_or_else(|| Error::MissingRegistration {
+            path: lib_path.clone(),
+            expected: "`pub mod day_N;` declarations",
+        })?;
+    let registry_path = src.join("registry.rs");
+    let registry = register_solution(&read(&registry_path)?, day)?.ok_or_else(|| {
+        Error::MissingRegistration {
+            path: registry_path.clone(),
+            expected: "`(YEAR, N) => crate::day_N::DayN` entries in `registry!`",
+        }
+    })?;
+
+    fs::create_dir_all(&module_dir).map_err(|e| Error::io(IoAction::CreateDir, &module_dir, e))?;
+    let files = [
+        (day_path, render(DAY_TEMPLATE, day)),
+        (module_dir.join("puzzle.rs"), render(PUZZLE_TEMPLATE, day)),
+        (module_dir.join("example.txt"), String::new()),
+        (lib_path, lib),
+        (registry_path, registry),
+    ];
+    for (path, contents) in &files {
+        fs::write(path, contents).map_err(|e| Error::io(IoAction::Write, path, e))?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|e| Error::io(IoAction::Read, path, e))
+}
+
+fn render(template: &str, day: u8) -> String {
+    template.replace(PLACEHOLDER, &day.to_string())
+}
+
+/// Adds the declaration of the module of the day to the root of the crate.
+fn register_module(lib: &str, day: u8) -> Result<Option<String>, Error> {
+    insert_sorted(lib, day, format!("pub mod day_{day};"), |line| {
+        line.strip_prefix("pub mod day_")?
+            .strip_suffix(';')?
+            .parse()
+            .ok()
+    })
+}
+
+/// Adds the solution of the day to the registry of the solutions of the year.
+fn register_solution(registry: &str, day: u8) -> Result<Option<String>, Error> {
+    let prefix = format!("({YEAR}, ");
+    insert_sorted(
+        registry,
+        day,
+        format!("    ({YEAR}, {day}) => crate::day_{day}::Day{day},"),
+        |line| {
+            line.trim()
+                .strip_prefix(&prefix)?
+                .split_once(") => ")?
+                .0
+                .parse()
+                .ok()
+        },
+    )
+}
+
+/// Inserts a line among the ones registering the other days, which are recognized by extracting
+/// their day, so that they stay sorted. Returns `None` if no day is registered in the source.
+fn insert_sorted(
+    source: &str,
+    day: u8,
+    line: String,
+    registered_day: impl Fn(&str) -> Option<u8>,
+) -> Result<Option<String>, Error> {
+    let mut lines = source.lines().collect::<Vec<_>>();
+    let registered = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, registered_day(line)?)))
+        .collect::<Vec<_>>();
+    if registered.iter().any(|(_, x)| *x == day) {
+        return Err(Error::DayAlreadyExists(day));
+    }
+    let Some(&(last, _)) = registered.last() else {
+        return Ok(None);
+    };
+    let index = registered
+        .iter()
+        .find(|(_, x)| *x > day)
+        .map_or(last + 1, |(i, _)| *i);
+    lines.insert(index, &line);
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    const LIB: &str = "pub mod answers;\npub mod day_1;\npub mod day_3;\npub mod error;\n";
+    const REGISTRY: &str = "registry! {\n    (2024, 5) => crate::day_5::Day5,\n    \
+        (2025, 1) => crate::day_1::Day1,\n    (2025, 3) => crate::day_3::Day3,\n}\n";
+
+    #[test]
+    fn test_register_module() {
+        assert_eq!(
+            register_module(LIB, 2).unwrap().unwrap(),
+            "pub mod answers;\npub mod day_1;\npub mod day_2;\npub mod day_3;\npub mod error;\n"
+        );
+        assert_eq!(
+            register_module(LIB, 12).unwrap().unwrap(),
+            "pub mod answers;\npub mod day_1;\npub mod day_3;\npub mod day_12;\npub mod error;\n"
+        );
+        assert!(matches!(
+            register_module(LIB, 3),
+            Err(Error::DayAlreadyExists(3))
+        ));
+        assert_eq!(register_module("pub mod error;\n", 1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_register_solution() {
+        assert_eq!(
+            register_solution(REGISTRY, 2).unwrap().unwrap(),
+            "registry! {\n    (2024, 5) => crate::day_5::Day5,\n    (2025, 1) => crate::day_1::Day1,\n    \
+            (2025, 2) => crate::day_2::Day2,\n    (2025, 3) => crate::day_3::Day3,\n}\n"
+        );
+        assert_eq!(
+            register_solution(REGISTRY, 5).unwrap().unwrap(),
+            "registry! {\n    (2024, 5) => crate::day_5::Day5,\n    (2025, 1) => crate::day_1::Day1,\n    \
+            (2025, 3) => crate::day_3::Day3,\n    (2025, 5) => crate::day_5::Day5,\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc2025-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/day_1")).unwrap();
+        fs::write(root.join("src/day_1.rs"), "").unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        fs::write(root.join("src/registry.rs"), REGISTRY).unwrap();
+
+        let files = new_day(&root, 2).unwrap();
+        assert_eq!(files.len(), 5);
+        let day = fs::read_to_string(root.join("src/day_2.rs")).unwrap();
+        assert!(day.contains("pub struct Day2;"));
+        assert!(day.contains("include_str!(\"day_2/example.txt\")"));
+        assert!(!day.contains(PLACEHOLDER));
+        assert!(root.join("src/day_2/puzzle.rs").exists());
+        assert!(root.join("src/day_2/example.txt").exists());
+        assert!(
+            fs::read_to_string(root.join("src/registry.rs"))
+                .unwrap()
+                .contains("(2025, 2) => crate::day_2::Day2,")
+        );
+
+        assert!(matches!(new_day(&root, 1), Err(Error::DayAlreadyExists(1))));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
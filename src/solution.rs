@@ -0,0 +1,41 @@
+use crate::Error;
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::runner::Part;
+use std::fmt::Display;
+
+/// Describes the solution to a single day of Advent of Code.
+/// Parsing the input is kept separate from solving each half of the puzzle, so that both halves
+/// can share the parsed input and their answers can be asserted on without going through stdout.
+pub trait Solution {
+    /// Representation of the puzzle input shared by both halves of the puzzle
+    type Input;
+    /// Answer to the first half of the puzzle
+    type Part1: Display;
+    /// Answer to the second half of the puzzle
+    type Part2: Display;
+
+    /// Title of the puzzle
+    const TITLE: &'static str;
+    /// Halves of the puzzle that have been solved so far. The others are never run
+    const IMPLEMENTED_PARTS: &'static [Part] = &[Part::One, Part::Two];
+    /// Human-readable description of the answer to the first half of the puzzle
+    const PART_1_LABEL: &'static str;
+    /// Human-readable description of the answer to the second half of the puzzle
+    const PART_2_LABEL: &'static str;
+
+    fn parse(input: &Input) -> Result<Self::Input, Error>;
+
+    /// Checks the input against every assumption the solution makes, listing all the violations
+    /// instead of stopping at the first one. Defaults to the error returned by [`Solution::parse`].
+    fn validate(input: &Input) -> Vec<ParseError> {
+        match Self::parse(input) {
+            Err(Error::Parse(e)) => vec![e],
+            _ => vec![],
+        }
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Self::Part1, Error>;
+
+    fn part_2(input: &Self::Input) -> Result<Self::Part2, Error>;
+}
@@ -0,0 +1,306 @@
+//! Local record of the answers submitted to the Advent of Code website, used to avoid submitting
+//! answers that are already known to be wrong, or submitting while rate-limited.
+
+use crate::Error;
+use crate::client::Outcome;
+use crate::error::IoAction;
+use crate::runner::Part;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::Duration;
+
+/// An answer that has been submitted, along with how it was judged.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Guess {
+    pub answer: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+    /// When the answer was submitted, in seconds since the Unix epoch
+    pub submitted_at: u64,
+}
+
+/// Submitted answers, stored in a TOML file with an array of guesses for each part of each day, e.g.:
+/// ```toml
+/// [[day_1.part_1]]
+/// answer = "1234"
+/// outcome = "too_high"
+/// wait_seconds = 60
+/// submitted_at = 1764547200
+/// ```
+#[derive(Debug, Default)]
+pub struct Submissions {
+    days: BTreeMap<String, BTreeMap<String, Vec<Guess>>>,
+}
+
+/// Why an answer shouldn't be submitted.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Refusal {
+    /// The part has already been solved with the given answer
+    Solved { answer: String },
+    /// The very same answer has already been judged wrong
+    KnownWrong(Outcome),
+    /// The answer is at least as high as one that has been judged too high
+    AboveTooHigh { bound: String },
+    /// The answer is at most as low as one that has been judged too low
+    BelowTooLow { bound: String },
+    /// The website asked to wait before submitting again
+    RateLimited { remaining: Duration },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Solved { answer } => write!(f, "the part was already solved with {answer}"),
+            Refusal::KnownWrong(outcome) => {
+                write!(f, "it was already submitted, and was {outcome}")
+            }
+            Refusal::AboveTooHigh { bound } => write!(f, "{bound} was already too high"),
+            Refusal::BelowTooLow { bound } => write!(f, "{bound} was already too low"),
+            Refusal::RateLimited { remaining } => write!(
+                f,
+                "the website asked to wait {}s more before submitting again",
+                remaining.as_secs()
+            ),
+        }
+    }
+}
+
+fn keys(day: u8, part: Part) -> (String, String) {
+    (format!("day_{day}"), format!("part_{part}"))
+}
+
+impl Submissions {
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        Ok(Self {
+            days: toml::from_str(s).map_err(Error::Submissions)?,
+        })
+    }
+
+    /// Loads the submissions from a file. A missing file is treated as an empty one.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::io(IoAction::Read, path, e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| Error::io(IoAction::CreateDir, dir, e))?;
+        }
+        let s = toml::to_string(&self.days).expect("Submissions are always serializable");
+        std::fs::write(path, s).map_err(|e| Error::io(IoAction::Write, path, e))
+    }
+
+    /// Returns the guesses submitted so far for a part of the puzzle of the given day, oldest first.
+    pub fn guesses(&self, day: u8, part: Part) -> &[Guess] {
+        let (day, part) = keys(day, part);
+        self.days
+            .get(&day)
+            .and_then(|x| x.get(&part))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn record(&mut self, day: u8, part: Part, guess: Guess) {
+        let (day, part) = keys(day, part);
+        self.days
+            .entry(day)
+            .or_default()
+            .entry(part)
+            .or_default()
+            .push(guess);
+    }
+
+    /// Checks whether the answer is worth submitting at the given time, in seconds since the Unix
+    /// epoch, given the previous guesses. Numeric answers are also compared with the ones that were
+    /// judged too high or too low.
+    pub fn check(&self, day: u8, part: Part, answer: &str, now: u64) -> Option<Refusal> {
+        let guesses = self.guesses(day, part);
+        if let Some(guess) = guesses.iter().find(|x| x.outcome == Outcome::Correct) {
+            return Some(Refusal::Solved {
+                answer: guess.answer.clone(),
+            });
+        }
+        if let Some(guess) = guesses
+            .iter()
+            .find(|x| x.answer == answer && x.outcome.is_wrong())
+        {
+            return Some(Refusal::KnownWrong(guess.outcome));
+        }
+        if let Some(guess) = guesses.last()
+            && let Some(remaining) = Duration::from_secs(guess.submitted_at)
+                .saturating_add(guess.outcome.wait())
+                .checked_sub(Duration::from_secs(now))
+            && !remaining.is_zero()
+        {
+            return Some(Refusal::RateLimited { remaining });
+        }
+
+        let value = answer.parse::<i128>().ok()?;
+        let bound = |outcome: fn(&Outcome) -> bool| {
+            guesses
+                .iter()
+                .filter(move |x| outcome(&x.outcome))
+                .filter_map(|x| Some((x.answer.parse::<i128>().ok()?, x)))
+        };
+        if let Some((_, guess)) = bound(|x| matches!(x, Outcome::TooHigh { .. }))
+            .filter(|(x, _)| value >= *x)
+            .min_by_key(|(x, _)| *x)
+        {
+            return Some(Refusal::AboveTooHigh {
+                bound: guess.answer.clone(),
+            });
+        }
+        bound(|x| matches!(x, Outcome::TooLow { .. }))
+            .filter(|(x, _)| value <= *x)
+            .max_by_key(|(x, _)| *x)
+            .map(|(_, guess)| Refusal::BelowTooLow {
+                bound: guess.answer.clone(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guess(answer: &str, outcome: Outcome, submitted_at: u64) -> Guess {
+        Guess {
+            answer: answer.to_string(),
+            outcome,
+            submitted_at,
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let mut submissions = Submissions::default();
+        assert_eq!(submissions.check(1, Part::One, "100", 0), None);
+        let too_high = Outcome::TooHigh { wait_seconds: 60 };
+        let too_low = Outcome::TooLow { wait_seconds: 60 };
+        let wrong = Outcome::Wrong { wait_seconds: 300 };
+        submissions.record(1, Part::One, guess("100", too_high, 0));
+        submissions.record(1, Part::One, guess("20", too_low, 60));
+        submissions.record(1, Part::One, guess("abc", wrong, 120));
+        assert_eq!(
+            submissions.check(1, Part::One, "abc", 1000),
+            Some(Refusal::KnownWrong(wrong))
+        );
+        // The wait asked for after a wrong answer has to expire before submitting again
+        assert_eq!(
+            submissions.check(1, Part::One, "50", 400),
+            Some(Refusal::RateLimited {
+                remaining: Duration::from_secs(20)
+            })
+        );
+        assert_eq!(submissions.check(1, Part::One, "50", 420), None);
+        assert_eq!(
+            submissions.check(1, Part::One, "150", 1000),
+            Some(Refusal::AboveTooHigh {
+                bound: "100".to_string()
+            })
+        );
+        assert_eq!(
+            submissions.check(1, Part::One, "20", 1000),
+            Some(Refusal::KnownWrong(too_low))
+        );
+        assert_eq!(
+            submissions.check(1, Part::One, "-3", 1000),
+            Some(Refusal::BelowTooLow {
+                bound: "20".to_string()
+            })
+        );
+        assert_eq!(submissions.check(1, Part::One, "50", 1000), None);
+        assert_eq!(submissions.check(1, Part::Two, "150", 1000), None);
+
+        submissions.record(
+            1,
+            Part::One,
+            guess("50", Outcome::RateLimited { wait_seconds: 30 }, 1000),
+        );
+        assert_eq!(
+            submissions.check(1, Part::One, "50", 1010),
+            Some(Refusal::RateLimited {
+                remaining: Duration::from_secs(20)
+            })
+        );
+        assert_eq!(submissions.check(1, Part::One, "50", 1030), None);
+
+        submissions.record(1, Part::One, guess("50", Outcome::Correct, 1030));
+        assert_eq!(
+            submissions.check(1, Part::One, "60", 2000),
+            Some(Refusal::Solved {
+                answer: "50".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path =
+            std::env::temp_dir().join(format!("aoc2025-submissions-{}.toml", std::process::id()));
+        let mut submissions = Submissions::default();
+        submissions.record(
+            3,
+            Part::Two,
+            guess("100", Outcome::TooHigh { wait_seconds: 60 }, 10),
+        );
+        submissions.record(
+            3,
+            Part::Two,
+            guess("90", Outcome::RateLimited { wait_seconds: 45 }, 20),
+        );
+        submissions.save(&path).unwrap();
+        let loaded = Submissions::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            loaded.guesses(3, Part::Two),
+            submissions.guesses(3, Part::Two)
+        );
+        assert!(loaded.guesses(3, Part::One).is_empty());
+    }
+
+    #[test]
+    fn test_save_reports_the_failing_path() {
+        // A file can't be the directory of the submissions
+        let error = Submissions::default()
+            .save(Path::new("Cargo.toml/submissions.toml"))
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            Error::Io {
+                action: IoAction::CreateDir,
+                ..
+            }
+        ));
+        assert!(
+            error
+                .to_string()
+                .starts_with("couldn't create the directory Cargo.toml: ")
+        );
+    }
+
+    #[test]
+    fn test_wrong_answer_without_wait() {
+        let submissions = Submissions::parse(
+            "[[day_1.part_1]]\nanswer = \"20\"\noutcome = \"too_low\"\nsubmitted_at = 60",
+        )
+        .unwrap();
+        assert_eq!(
+            submissions.guesses(1, Part::One),
+            [guess("20", Outcome::TooLow { wait_seconds: 0 }, 60)]
+        );
+    }
+
+    #[test]
+    fn test_invalid_submissions_file() {
+        assert!(matches!(
+            Submissions::parse("[[day_1.part_1]]\nanswer = 1"),
+            Err(Error::Submissions(_))
+        ));
+    }
+}
@@ -0,0 +1,30 @@
+//! Instrumentation of the hot paths of the solutions.
+//!
+//! Formatting the fields of events emitted in tight loops can dominate the runtime of a solution,
+//! so these events are only kept in debug builds, or when the `trace` feature is enabled. Otherwise
+//! they're dead code, which the compiler strips out along with the formatting of their fields.
+//! Spans on hot functions are gated the same way with
+//! `#[cfg_attr(any(debug_assertions, feature = "trace"), tracing::instrument)]`.
+
+/// Whether the instrumentation of hot paths is compiled in
+pub const ENABLED: bool = cfg!(any(debug_assertions, feature = "trace"));
+
+/// Emits a [`tracing::debug!`] event from a hot path, if its instrumentation is [`ENABLED`].
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        if $crate::trace::ENABLED {
+            tracing::debug!($($arg)+);
+        }
+    };
+}
+
+/// Emits a [`tracing::info!`] event from a hot path, if its instrumentation is [`ENABLED`].
+macro_rules! info {
+    ($($arg:tt)+) => {
+        if $crate::trace::ENABLED {
+            tracing::info!($($arg)+);
+        }
+    };
+}
+
+pub(crate) use {debug, info};